@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::solution::{parse_token, ParseError};
+
+/// Known puzzle answers, keyed by day and part
+///
+/// Read from a small TOML subset: one `[dayNN]` table per day holding
+/// `part1`/`part2` keys with integer or string values, plus `#` comments.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+/// Outcome of comparing a computed answer with the stored one
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // `pad` so callers can align the verdict column with `{:<8}`
+        f.pad(match self {
+            Verdict::Pass => "PASS",
+            Verdict::Fail { .. } => "FAIL",
+            Verdict::Missing => "MISSING",
+        })
+    }
+}
+
+/// Strip a trailing `# comment` that isn't inside a string
+fn strip_comment(text: &str) -> &str {
+    let mut in_string = false;
+    for (idx, c) in text.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &text[..idx],
+            _ => {}
+        }
+    }
+    text
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Answers, ParseError> {
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+
+        for (idx, raw) in input.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = strip_comment(raw).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[') {
+                let name = table
+                    .strip_suffix(']')
+                    .ok_or_else(|| ParseError::at(line_no, raw, &table[table.len()..], "`]`"))?;
+                let number = name
+                    .trim()
+                    .strip_prefix("day")
+                    .ok_or_else(|| ParseError::at(line_no, raw, name, "a table named `dayNN`"))?;
+                day = Some(parse_token::<u8>(line_no, raw, number, "a day number")?);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::at(line_no, raw, line, "`part1 = ...` or `part2 = ...`"))?;
+            let key = key.trim();
+            let part = match key {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(ParseError::at(line_no, raw, key, "`part1` or `part2`")),
+            };
+            let day = day.ok_or_else(|| ParseError::at(line_no, raw, key, "a `[dayNN]` table first"))?;
+
+            let value = value.trim();
+            let answer = match value.strip_prefix('"') {
+                Some(quoted) => quoted
+                    .strip_suffix('"')
+                    .filter(|text| !text.contains('"'))
+                    .ok_or_else(|| ParseError::at(line_no, raw, value, "a closing `\"`"))?
+                    .to_string(),
+                None => parse_token::<i128>(line_no, raw, value, "an integer or a quoted string")?.to_string(),
+            };
+            answers.insert((day, part), answer);
+        }
+
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn verdict(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse(
+            "# known answers\n\
+             [day01]\n\
+             part1 = 1234\n\
+             part2 = \"5678\"  # as a string\n\
+             \n\
+             [day12]\n\
+             part1 = 403\n",
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, 1), Some("1234"));
+        assert_eq!(answers.get(1, 2), Some("5678"));
+        assert_eq!(answers.get(12, 1), Some("403"));
+        assert_eq!(answers.get(12, 2), None);
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let answers = Answers::parse("[day3]\npart1 = 357\n").unwrap();
+        assert_eq!(answers.verdict(3, 1, "357"), Verdict::Pass);
+        assert_eq!(
+            answers.verdict(3, 1, "358"),
+            Verdict::Fail { expected: "357".to_string() }
+        );
+        assert_eq!(answers.verdict(3, 2, "1"), Verdict::Missing);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Answers::parse("part1 = 3\n"),
+            Err(ParseError::new(1, 1, "a `[dayNN]` table first", "`part1`"))
+        );
+        assert_eq!(
+            Answers::parse("[day01]\npart3 = 3\n"),
+            Err(ParseError::new(2, 1, "`part1` or `part2`", "`part3`"))
+        );
+        assert_eq!(
+            Answers::parse("[day01]\npart1 = 3x\n"),
+            Err(ParseError::new(2, 9, "an integer or a quoted string", "`3x`"))
+        );
+        assert_eq!(
+            Answers::parse("[dayx]\n"),
+            Err(ParseError::new(1, 5, "a day number", "`x`"))
+        );
+        assert_eq!(
+            Answers::parse("[week1]\n"),
+            Err(ParseError::new(1, 2, "a table named `dayNN`", "`week1`"))
+        );
+    }
+}
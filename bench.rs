@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::solution::{parse_token, DynSolution, ParseError};
+
+/// How often each phase runs: untimed warm-up runs first, then timed iterations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: 3,
+            iterations: 20,
+        }
+    }
+}
+
+/// Summary of the timed iterations of one phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Nearest-rank percentiles of `samples`, which must not be empty
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        samples.sort_unstable();
+        let rank = |percent: usize| samples[(samples.len() * percent).div_ceil(100).max(1) - 1];
+        Stats {
+            min: samples[0],
+            median: rank(50),
+            p95: rank(95),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Timings of one phase (`parse`, `part1` or `part2`) of one day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseStats {
+    pub day: u8,
+    pub phase: String,
+    pub stats: Stats,
+}
+
+fn measure(config: BenchConfig, mut run: impl FnMut()) -> Stats {
+    for _ in 0..config.warmup {
+        run();
+    }
+    let mut samples: Vec<Duration> = (0..config.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(&mut samples)
+}
+
+/// Time parsing and the selected parts of one day on an input that is already in memory
+/// Part timings include rendering the answer as text; parts a day doesn't have are skipped
+pub fn bench_day(
+    solution: &dyn DynSolution,
+    input: &str,
+    parts: &[u8],
+    config: BenchConfig,
+) -> Result<Vec<PhaseStats>, ParseError> {
+    let parsed = solution.parse(input)?;
+    let phase = |name: &str, stats| PhaseStats {
+        day: solution.day(),
+        phase: name.to_string(),
+        stats,
+    };
+
+    let mut phases = vec![phase(
+        "parse",
+        measure(config, || {
+            let _ = black_box(solution.parse(black_box(input)));
+        }),
+    )];
+    for &part in parts {
+        match part {
+            1 => phases.push(phase("part1", measure(config, || {
+                black_box(parsed.part1());
+            }))),
+            _ => {
+                // A day without part 2 answers `None` at once, so timing it costs nothing and
+                // tells whether there is a part 2 without an extra untimed solve
+                let mut answered = false;
+                let stats = measure(config, || {
+                    answered = black_box(parsed.part2()).is_some();
+                });
+                if answered {
+                    phases.push(phase("part2", stats));
+                }
+            }
+        }
+    }
+    Ok(phases)
+}
+
+/// Saved timings of an earlier run, keyed by day and phase
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    stats: BTreeMap<(u8, String), Stats>,
+}
+
+impl Baseline {
+    /// Parse the whitespace-separated `day phase min median p95 max` lines (nanoseconds)
+    /// written by `format_baseline`; `#` starts a comment line
+    pub fn parse(input: &str) -> Result<Baseline, ParseError> {
+        let mut stats = BTreeMap::new();
+
+        for (idx, line) in input.lines().enumerate() {
+            let line_no = idx + 1;
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 6 {
+                let found = fields.get(6).copied().unwrap_or(&line[line.len()..]);
+                return Err(ParseError::at(line_no, line, found, "`day phase min median p95 max`"));
+            }
+            let day = parse_token::<u8>(line_no, line, fields[0], "a day number")?;
+            let nanos = |field: &str| {
+                parse_token::<u64>(line_no, line, field, "a duration in nanoseconds").map(Duration::from_nanos)
+            };
+            let entry = Stats {
+                min: nanos(fields[2])?,
+                median: nanos(fields[3])?,
+                p95: nanos(fields[4])?,
+                max: nanos(fields[5])?,
+            };
+            stats.insert((day, fields[1].to_string()), entry);
+        }
+
+        Ok(Baseline { stats })
+    }
+
+    pub fn get(&self, day: u8, phase: &str) -> Option<&Stats> {
+        self.stats.get(&(day, phase.to_string()))
+    }
+}
+
+/// Render timings in the format read by `Baseline::parse`
+pub fn format_baseline(phases: &[PhaseStats]) -> String {
+    let mut text = String::from("# day phase min_ns median_ns p95_ns max_ns\n");
+    for phase in phases {
+        let stats = &phase.stats;
+        text += &format!(
+            "{:02} {} {} {} {} {}\n",
+            phase.day,
+            phase.phase,
+            stats.min.as_nanos(),
+            stats.median.as_nanos(),
+            stats.p95.as_nanos(),
+            stats.max.as_nanos()
+        );
+    }
+    text
+}
+
+/// Relative change of the median against the baseline, e.g. `+12.5%`
+fn median_change(current: &Stats, baseline: &Stats) -> String {
+    if baseline.median.is_zero() {
+        return "n/a".to_string();
+    }
+    let ratio = current.median.as_secs_f64() / baseline.median.as_secs_f64();
+    format!("{:+.1}%", (ratio - 1.0) * 100.0)
+}
+
+pub fn print_header(baseline: bool) {
+    let compare = if baseline { "  vs baseline" } else { "" };
+    println!(
+        "{:<4} {:<6} {:>10} {:>10} {:>10} {:>10}{}",
+        "Day", "Phase", "min", "median", "p95", "max", compare
+    );
+}
+
+pub fn print_row(phase: &PhaseStats, baseline: Option<&Baseline>) {
+    let stats = &phase.stats;
+    let compare = match baseline {
+        Some(baseline) => match baseline.get(phase.day, &phase.phase) {
+            Some(before) => format!("  {:>11}", median_change(stats, before)),
+            None => format!("  {:>11}", "new"),
+        },
+        None => String::new(),
+    };
+    println!(
+        "{:<4} {:<6} {:>10} {:>10} {:>10} {:>10}{}",
+        format!("{:02}", phase.day),
+        phase.phase,
+        format!("{:.1?}", stats.min),
+        format!("{:.1?}", stats.median),
+        format!("{:.1?}", stats.p95),
+        format!("{:.1?}", stats.max),
+        compare
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&n| Duration::from_nanos(n)).collect()
+    }
+
+    #[test]
+    fn test_stats_nearest_rank() {
+        let stats = Stats::from_samples(&mut nanos(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.p95, Duration::from_nanos(5));
+        assert_eq!(stats.max, Duration::from_nanos(5));
+
+        let mut samples = nanos(&(1..=100).collect::<Vec<_>>());
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.median, Duration::from_nanos(50));
+        assert_eq!(stats.p95, Duration::from_nanos(95));
+
+        let stats = Stats::from_samples(&mut nanos(&[7]));
+        assert_eq!((stats.min, stats.median, stats.p95), (stats.max, stats.max, stats.max));
+    }
+
+    #[test]
+    fn test_bench_day_phases() {
+        let config = BenchConfig { warmup: 1, iterations: 3 };
+        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n";
+        let phases = bench_day(registry::find(1).unwrap(), input, &[1, 2], config).unwrap();
+        let names: Vec<&str> = phases.iter().map(|phase| phase.phase.as_str()).collect();
+        assert_eq!(names, ["parse", "part1", "part2"]);
+        assert!(phases.iter().all(|phase| phase.stats.min <= phase.stats.max));
+
+        assert!(bench_day(registry::find(1).unwrap(), "X1\n", &[1], config).is_err());
+
+        // Day 12 has no part 2, so there is nothing to report for it
+        let day12 = registry::find(12).unwrap();
+        let input = day12.generate(&mut crate::rng::Rng::new(1), 1).unwrap();
+        let phases = bench_day(day12, &input, &[1, 2], config).unwrap();
+        let names: Vec<&str> = phases.iter().map(|phase| phase.phase.as_str()).collect();
+        assert_eq!(names, ["parse", "part1"]);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let phases = vec![PhaseStats {
+            day: 4,
+            phase: "part2".to_string(),
+            stats: Stats::from_samples(&mut nanos(&[10, 30, 20])),
+        }];
+        let text = format_baseline(&phases);
+        assert_eq!(text.lines().nth(1), Some("04 part2 10 20 30 30"));
+
+        let baseline = Baseline::parse(&text).unwrap();
+        assert_eq!(baseline.get(4, "part2"), Some(&phases[0].stats));
+        assert_eq!(baseline.get(4, "part1"), None);
+        assert_eq!(median_change(&phases[0].stats, &phases[0].stats), "+0.0%");
+    }
+
+    #[test]
+    fn test_baseline_errors() {
+        assert_eq!(
+            Baseline::parse("01 parse 1 2 3\n"),
+            Err(ParseError::new(1, 15, "`day phase min median p95 max`", "end of line"))
+        );
+        assert_eq!(
+            Baseline::parse("01 parse 1 2x 3 4\n"),
+            Err(ParseError::new(1, 12, "a duration in nanoseconds", "`2x`"))
+        );
+    }
+}
@@ -1,135 +1,385 @@
-use std::path::Path;
+use std::fmt::{self, Display, Write};
 
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
+use crate::rng::Rng;
+use crate::solution::{parse_token, ParseError, Solution};
+use crate::trace::Trace;
 
-fn part1(lines: &[String]) -> i64 {
-    let mut password: i64 = 0;
-    let mut actual_position: i64 = 50;
-    let modulo = 100;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Left(i64),
+    Right(i64),
+    /// Set the dial straight to a position, without clicking through the ones between
+    Set(i64),
+}
 
-    for line in lines {
-        let chars: Vec<char> = line.chars().collect();
-        let direction = chars[0];
-        let num_str = &line[1..];
-        let num: i64 = num_str.parse().unwrap();
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Move::Left(num) => write!(f, "L{}", num),
+            Move::Right(num) => write!(f, "R{}", num),
+            Move::Set(position) => write!(f, "={}", position),
+        }
+    }
+}
 
-        if direction == 'L' {
-            actual_position -= num;
-        } else {
-            actual_position += num;
+/// Largest dial size, so that a position plus a distance of up to `u32::MAX` fits an i64
+pub const MAX_DIAL_SIZE: i64 = 1 << 62;
+
+/// Dial numbered `0..size` that starts at `start` and counts visits to any of `targets`
+/// The puzzle's dial has 100 positions, starts at 50 and counts zeros
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dial {
+    /// From 1 to `MAX_DIAL_SIZE`
+    pub size: i64,
+    pub start: i64,
+    /// Distinct positions in `0..size`
+    pub targets: Vec<i64>,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Dial { size: 100, start: 50, targets: vec![0] }
+    }
+}
+
+/// What one move did to the dial
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub movement: Move,
+    pub start: i64,
+    pub end: i64,
+    /// Clicks of this move that left the dial on a target, including the last one; a
+    /// `Set` onto a target counts once
+    pub crossings: i64,
+    /// Whether the move ended on a target
+    pub landed: bool,
+}
+
+impl Dial {
+    /// Result of each move in turn, starting from `start`
+    pub fn steps<'a>(&'a self, moves: &'a [Move]) -> impl Iterator<Item = Step> + 'a {
+        // Positions reached in (low, high] that are `target` modulo the dial size
+        let hits = |low: i64, high: i64, target: i64| {
+            (high - target).div_euclid(self.size) - (low - target).div_euclid(self.size)
+        };
+
+        moves.iter().scan(self.start, move |position, &movement| {
+            let start = *position;
+            // Turning left visits the positions from `start - num` up to just before
+            // `start`; shifting that range by one gives the same hits as a right turn
+            let (low, high, unwrapped) = match movement {
+                Move::Left(num) => (start - num - 1, start - 1, start - num),
+                Move::Right(num) => (start, start + num, start + num),
+                Move::Set(to) => (to - 1, to, to),
+            };
+            let end = unwrapped.rem_euclid(self.size);
+            *position = end;
+            Some(Step {
+                movement,
+                start,
+                end,
+                crossings: self.targets.iter().map(|&target| hits(low, high, target)).sum(),
+                landed: self.targets.contains(&end),
+            })
+        })
+    }
+
+    /// Number of moves that end exactly on a target
+    pub fn count_landings(&self, moves: &[Move]) -> i64 {
+        self.steps(moves).filter(|step| step.landed).count() as i64
+    }
+
+    /// Number of clicks, over all moves, that leave the dial on a target
+    pub fn count_passes(&self, moves: &[Move]) -> i64 {
+        self.steps(moves).map(|step| step.crossings).sum()
+    }
+
+    /// Distance to turn right from `position` to stop on the `nth` target ahead (1-based),
+    /// or `None` if it overflows
+    fn distance_to_target(&self, position: i64, nth: i64) -> Option<i64> {
+        let count = self.targets.len() as i64;
+        let first = self.targets.partition_point(|&target| target <= position) as i64;
+        let idx = first.checked_add(nth - 1)?;
+        let offset = self.targets[idx.rem_euclid(count) as usize] - position;
+        idx.div_euclid(count).checked_mul(self.size)?.checked_add(offset)
+    }
+
+    /// Distance to turn right from `position` to stop just short of the target after the
+    /// `passes`-th one ahead, if that spot isn't a target itself and the distance fits
+    fn distance_between_targets(&self, position: i64, passes: i64) -> Option<i64> {
+        let distance = self.distance_to_target(position, passes.checked_add(1)?)? - 1;
+        let end = (position + distance % self.size).rem_euclid(self.size);
+        (distance > 0 && !self.targets.contains(&end)).then_some(distance)
+    }
+
+    /// Moves that land on a target exactly `landings` times and leave the dial on one
+    /// exactly `passes` times (the two answers of the puzzle), using at most `budget`
+    /// moves. A count left as `None` takes whatever value needs the fewest moves. The
+    /// result is always checked with `count_landings` and `count_passes`.
+    ///
+    /// Any counts work with at least one landing. Without landings every move has to stop
+    /// off the targets, which on a crowded dial rules out some pass counts.
+    pub fn find_moves(&self, landings: Option<i64>, passes: Option<i64>, budget: usize) -> Result<Vec<Move>, String> {
+        let (landings, passes) = match (landings, passes) {
+            (Some(landings), Some(passes)) => (landings, passes),
+            (Some(landings), None) => (landings, landings),
+            (None, Some(passes)) => (0, passes),
+            (None, None) => return Err("give a number of landings, passes or both".to_string()),
+        };
+        if landings < 0 || passes < landings {
+            return Err(format!("{} passes can't include {} landings", passes, landings));
+        }
+        if passes > 0 && self.targets.is_empty() {
+            return Err("the dial has no targets to pass".to_string());
         }
+        // Checked before building any move, so a huge count fails fast instead of filling memory
+        let fewest = if landings > 0 { landings } else { i64::from(passes > 0) };
+        if usize::try_from(fewest).map_or(true, |fewest| fewest > budget) {
+            return Err(format!("needs at least {} moves, more than the budget of {}", fewest, budget));
+        }
+        let too_long = || "needs a turn longer than an input line can hold".to_string();
 
-        actual_position = actual_position.rem_euclid(modulo);
+        let mut moves = Vec::new();
+        if landings > 0 {
+            // The first landing also takes every pass that isn't a landing, so the
+            // remaining ones only need to reach the next target
+            let first_nth = (passes - landings).checked_add(1).ok_or_else(too_long)?;
+            let mut position = self.start;
+            for nth in std::iter::once(first_nth).chain((1..landings).map(|_| 1)) {
+                let distance = self
+                    .distance_to_target(position, nth)
+                    .filter(|&distance| distance <= i64::from(u32::MAX))
+                    .ok_or_else(too_long)?;
+                moves.push(Move::Right(distance));
+                position = (position + distance).rem_euclid(self.size);
+            }
+        } else if passes > 0 {
+            // Two moves covering every pass turn at least as far as one move landing on the
+            // last target, so if even that overflows one of them is too long
+            self.distance_to_target(self.start, passes).ok_or_else(too_long)?;
+            // Stop between two targets; if the gap after the last pass is shut, stop
+            // earlier and make up the difference in a second move
+            moves = (0..passes.min(self.targets.len() as i64))
+                .find_map(|first| {
+                    let mut moves = Vec::new();
+                    let mut position = self.start;
+                    for count in [first, passes - first].into_iter().filter(|&count| count > 0) {
+                        let distance = self.distance_between_targets(position, count)?;
+                        moves.push(Move::Right(distance));
+                        position = (position + distance % self.size).rem_euclid(self.size);
+                    }
+                    Some(moves)
+                })
+                .ok_or_else(|| "no position between targets to stop on".to_string())?;
+        }
 
-        if actual_position == 0 {
-            password += 1;
+        if moves.len() > budget {
+            return Err(format!("needs {} moves, more than the budget of {}", moves.len(), budget));
+        }
+        if let Some(turn) = moves.iter().find(|turn| matches!(turn, Move::Right(distance) if *distance > i64::from(u32::MAX))) {
+            return Err(format!("needs the turn {}, longer than an input line can hold", turn));
         }
+        let counts = (self.count_landings(&moves), self.count_passes(&moves));
+        if counts != (landings, passes) {
+            return Err(format!(
+                "found moves giving {} landings and {} passes instead of {} and {}",
+                counts.0, counts.1, landings, passes
+            ));
+        }
+        Ok(moves)
     }
+}
 
-    password
+/// Moves together with the dial they turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rotations {
+    pub dial: Dial,
+    pub moves: Vec<Move>,
+    /// Input line of each move
+    pub lines: Vec<usize>,
 }
 
+impl Rotations {
+    /// Table of every move, to find by inspection the one that was miscounted
+    pub fn trace(&self) -> Trace {
+        let mut trace = Trace::new(vec!["move", "line", "rotation", "start", "end", "crossings", "landed"]);
+        for (idx, step) in self.dial.steps(&self.moves).enumerate() {
+            trace.rows.push(vec![
+                (idx + 1).to_string(),
+                self.lines[idx].to_string(),
+                step.movement.to_string(),
+                step.start.to_string(),
+                step.end.to_string(),
+                step.crossings.to_string(),
+                if step.landed { "yes" } else { "no" }.to_string(),
+            ]);
+        }
+        trace
+    }
+}
 
-fn part2(lines: &[String]) -> i64 {
-    let mut password: i64 = 0;
-    let mut actual_position: i64 = 50;
+/// `text`, a slice of `line`, is one move: `L` or `R` and a distance, or `=` and the
+/// position on a dial of `size` to set it to. `L0` and `R0` leave the dial where it is.
+fn parse_move(line_no: usize, line: &str, text: &str, size: i64) -> Result<Move, ParseError> {
+    let direction = text.chars().next();
+    let (direction_str, num_str) = text.split_at(direction.map_or(0, char::len_utf8));
+    let distance = || parse_token::<u32>(line_no, line, num_str, "a distance").map(i64::from);
 
-    for line in lines {
-        let chars: Vec<char> = line.chars().collect();
-        let direction = chars[0];
-        let num_str = &line[1..];
-        let num: i64 = num_str.parse().unwrap();
-        let modulo = 100;
+    match direction {
+        Some('L') => Ok(Move::Left(distance()?)),
+        Some('R') => Ok(Move::Right(distance()?)),
+        Some('=') => {
+            let position = parse_token(line_no, line, num_str, "a dial position")?;
+            if !(0..size).contains(&position) {
+                return Err(ParseError::at(line_no, line, num_str, "a position on the dial"));
+            }
+            Ok(Move::Set(position))
+        }
+        _ => Err(ParseError::at(line_no, line, direction_str, "`L`, `R` or `=`")),
+    }
+}
 
-        let old_pos = actual_position;
-        
-        if direction == 'L' {
-            actual_position -= num;
-        } else {
-            actual_position += num;
-        }
-        
-        let crosses = if old_pos != actual_position {
-            let old = old_pos;
-            let new = actual_position;
-
-            if old < new {
-                let first = (old.div_euclid(modulo) + 1) * modulo;
-                let last = new.div_euclid(modulo) * modulo;
-                if first <= new {
-                    (last - first) / modulo + 1
-                } else {
-                    0
-                }
-            } else {
-                let first = if new.rem_euclid(modulo) == 0 {
-                    new
-                } else {
-                    (new.div_euclid(modulo) + 1) * modulo
-                };
-                let last = ((old - 1).div_euclid(modulo)) * modulo;
-                if first <= last {
-                    (last - first) / modulo + 1
-                } else {
-                    0
+/// Moves of an input for the puzzle's dial; see `parse_rotations` for the syntax
+pub fn parse_moves<I>(lines: I) -> Result<Vec<Move>, ParseError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Ok(parse_rotations(lines)?.moves)
+}
+
+/// Settings line such as `dial size=360 start=0 targets=0,90,180`; keys may come in any
+/// order and missing ones keep the puzzle's values
+fn parse_dial(line_no: usize, line: &str, text: &str) -> Result<Dial, ParseError> {
+    let mut dial = Dial::default();
+    let mut start_token = None;
+    let mut target_tokens = None;
+
+    for setting in text.split_whitespace().skip(1) {
+        let (key, value) = setting.split_once('=').unwrap_or((setting, ""));
+        match key {
+            "size" => {
+                dial.size = parse_token(line_no, line, value, "a dial size")?;
+                if !(1..=MAX_DIAL_SIZE).contains(&dial.size) {
+                    return Err(ParseError::at(line_no, line, value, "a dial size from 1 to 2^62"));
                 }
             }
+            "start" => {
+                dial.start = parse_token(line_no, line, value, "a start position")?;
+                start_token = Some(value);
+            }
+            "targets" => {
+                let tokens: Vec<&str> = value.split(',').collect();
+                dial.targets = tokens
+                    .iter()
+                    .map(|token| parse_token(line_no, line, token, "a target position"))
+                    .collect::<Result<_, _>>()?;
+                target_tokens = Some(tokens);
+            }
+            _ => return Err(ParseError::at(line_no, line, setting, "`size=`, `start=` or `targets=`")),
+        }
+    }
+
+    // Positions are checked once every key is known, as `size` may come last
+    let on_dial = 0..dial.size;
+    if let Some(token) = start_token.filter(|_| !on_dial.contains(&dial.start)) {
+        return Err(ParseError::at(line_no, line, token, "a start position on the dial"));
+    }
+    for (idx, target) in dial.targets.iter().enumerate() {
+        if !on_dial.contains(target) {
+            let token = target_tokens.as_ref().map_or("", |tokens| tokens[idx]);
+            return Err(ParseError::at(line_no, line, token, "a target position on the dial"));
+        }
+    }
+    dial.targets.sort_unstable();
+    dial.targets.dedup();
+    Ok(dial)
+}
+
+/// One move per line, optionally preceded by a `dial ...` line describing a variant of
+/// the dial. `#` starts a comment; blank lines and blanks around a move are ignored.
+pub fn parse_rotations<I>(lines: I) -> Result<Rotations, ParseError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut rotations = Rotations { dial: Dial::default(), moves: Vec::new(), lines: Vec::new() };
+    let mut settings_allowed = true;
+    for (idx, line) in lines.into_iter().enumerate() {
+        let (line, line_no) = (line.as_ref(), idx + 1);
+        let text = line.split('#').next().unwrap_or_default().trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        if settings_allowed && text.split_whitespace().next() == Some("dial") {
+            rotations.dial = parse_dial(line_no, line, text)?;
         } else {
-            0
-        };
-        
-        password += crosses;
+            rotations.moves.push(parse_move(line_no, line, text, rotations.dial.size)?);
+            rotations.lines.push(line_no);
+        }
+        settings_allowed = false;
     }
-    
-    password
+    Ok(rotations)
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let lines = utils::read_lines(&input_path);
+pub fn part1(moves: &[Move]) -> i64 {
+    Dial::default().count_landings(moves)
+}
 
-    println!("Part 1: {}", part1(&lines));
-    println!("Part 2: {}", part2(&lines));
+pub fn part2(moves: &[Move]) -> i64 {
+    Dial::default().count_passes(moves)
+}
+
+/// Random dial rotations, about 4500 per unit of `scale` like a real input
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..4500 * scale {
+        let direction = if rng.chance(1, 2) { 'L' } else { 'R' };
+        writeln!(input, "{}{}", direction, rng.range(1..=999)).unwrap();
+    }
+    input
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Rotations;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_rotations(input.lines())
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        input.dial.count_landings(&input.moves)
+    }
+
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(input.dial.count_passes(&input.moves))
+    }
+
+    fn trace(input: &Self::Input) -> Option<Trace> {
+        Some(input.trace())
+    }
+
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use crate::property;
 
     #[test]
-    fn test_part1() {
-        let input = vec![
-            "L68".to_string(),
-            "L30".to_string(),
-            "R48".to_string(),
-            "L5".to_string(),
-            "R60".to_string(),
-            "L55".to_string(),
-            "L1".to_string(),
-            "L99".to_string(),
-            "R14".to_string(),
-            "L82".to_string(),
-        ];
-        assert_eq!(part1(&input), 3);
-    }
-
-    #[test]
-    fn test_part2() {
-        let input = vec![
-            "L68".to_string(),
-            "L30".to_string(),
-            "R48".to_string(),
-            "L5".to_string(),
-            "R60".to_string(),
-            "L55".to_string(),
-            "L1".to_string(),
-            "L99".to_string(),
-            "R14".to_string(),
-            "L82".to_string(),
-        ];
-        assert_eq!(part2(&input), 6);
+    fn test_examples() {
+        examples::assert_examples::<Day01>(include_str!("README.md"));
     }
 
     #[test]
@@ -140,7 +390,7 @@ mod tests {
             "L100".to_string(),
             "R200".to_string(),
         ];
-        assert_eq!(part2(&input), 5);
+        assert_eq!(part2(&parse_moves(&input).unwrap()), 5);
     }
 
     #[test]
@@ -149,7 +399,7 @@ mod tests {
             "R250".to_string(),
             "L500".to_string(),
         ];
-        assert_eq!(part2(&input), 8);
+        assert_eq!(part2(&parse_moves(&input).unwrap()), 8);
     }
 
     #[test]
@@ -159,7 +409,7 @@ mod tests {
             "R100".to_string(),
             "L200".to_string(),
         ];
-        assert_eq!(part2(&input), 4);
+        assert_eq!(part2(&parse_moves(&input).unwrap()), 4);
     }
 
     #[test]
@@ -170,6 +420,233 @@ mod tests {
             "L100".to_string(),
             "L100".to_string(),
         ];
-        assert_eq!(part2(&input), 4);
+        assert_eq!(part2(&parse_moves(&input).unwrap()), 4);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = vec!["L10".to_string(), "X10".to_string()];
+        assert_eq!(parse_moves(&input), Err(ParseError::new(2, 1, "`L`, `R` or `=`", "`X`")));
+
+        let input = vec!["R1x".to_string()];
+        assert_eq!(parse_moves(&input), Err(ParseError::new(1, 2, "a distance", "`1x`")));
+
+        assert_eq!(parse_moves(["  L 5"]), Err(ParseError::new(1, 4, "a distance", "` 5`")));
+        assert_eq!(parse_moves(["R-5"]), Err(ParseError::new(1, 2, "a distance", "`-5`")));
+        assert_eq!(parse_moves(["=100"]), Err(ParseError::new(1, 2, "a position on the dial", "`100`")));
+        assert_eq!(parse_moves(["=", "L1"]), Err(ParseError::new(1, 2, "a dial position", "end of line")));
+        assert_eq!(parse_moves(["# only a comment", "l4"]), Err(ParseError::new(2, 1, "`L`, `R` or `=`", "`l`")));
+    }
+
+    #[test]
+    fn test_move_syntax() {
+        let input = "# warm-up\n\nL5\n  R10   # clockwise\n=0\n\tR0\n";
+        let rotations = parse_rotations(input.lines()).unwrap();
+        assert_eq!(rotations.moves, [Move::Left(5), Move::Right(10), Move::Set(0), Move::Right(0)]);
+        assert_eq!(rotations.lines, [3, 4, 5, 6]);
+
+        // A set counts once when it lands on a target, and so does staying on one
+        let steps: Vec<(i64, i64, bool)> = Dial::default()
+            .steps(&rotations.moves)
+            .map(|step| (step.end, step.crossings, step.landed))
+            .collect();
+        assert_eq!(steps, [(45, 0, false), (55, 0, false), (0, 1, true), (0, 0, true)]);
+
+        let rotations = parse_rotations(["# variant", "dial size=10", "=9", "R1"]).unwrap();
+        assert_eq!((rotations.dial.size, rotations.moves.len()), (10, 2));
+        assert_eq!(parse_rotations(["=9", "dial size=10"]).unwrap_err().line, 2);
+        assert_eq!(parse_rotations(["dial size=10", "=10"]).unwrap_err().column, 2);
+    }
+
+    /// Signed distances, negative for left, so shrinking keeps each move meaningful
+    fn moves_from(distances: &[i64]) -> Vec<Move> {
+        distances
+            .iter()
+            .map(|&distance| if distance < 0 { Move::Left(-distance) } else { Move::Right(distance) })
+            .collect()
+    }
+
+    /// Turn the dial one click at a time and count every click that lands on a target
+    fn naive_passes(dial: &Dial, moves: &[Move]) -> i64 {
+        let mut position = dial.start;
+        let mut password = 0;
+        for step in moves {
+            let (clicks, delta) = match *step {
+                Move::Left(num) => (num, -1),
+                Move::Right(num) => (num, 1),
+                Move::Set(to) => {
+                    position = to;
+                    password += i64::from(dial.targets.contains(&to));
+                    continue;
+                }
+            };
+            for _ in 0..clicks {
+                position = (position + delta).rem_euclid(dial.size);
+                if dial.targets.contains(&position) {
+                    password += 1;
+                }
+            }
+        }
+        password
+    }
+
+    fn naive_part2(moves: &[Move]) -> i64 {
+        naive_passes(&Dial::default(), moves)
+    }
+
+    #[test]
+    fn test_part2_matches_naive() {
+        property::assert_same(
+            "day01 part2",
+            |rng| (0..rng.range(0..=30)).map(|_| rng.range(-350..=350)).collect::<Vec<i64>>(),
+            |distances| part2(&moves_from(distances)),
+            |distances| naive_part2(&moves_from(distances)),
+        );
+    }
+
+    /// Any dial from shrinkable numbers: sizes from 1, everything else wrapped onto it
+    fn dial_from(&((size, start), ref targets): &((i64, i64), Vec<i64>)) -> Dial {
+        let size = 1 + size.rem_euclid(40);
+        let mut targets: Vec<i64> = targets.iter().map(|target| target.rem_euclid(size)).collect();
+        targets.sort_unstable();
+        targets.dedup();
+        Dial { size, start: start.rem_euclid(size), targets }
+    }
+
+    /// Signed distances as in `moves_from`, except that a kind of 3 (mod 4) sets the dial
+    fn mixed_moves(dial: &Dial, moves: &[(i64, i64)]) -> Vec<Move> {
+        moves
+            .iter()
+            .map(|&(kind, value)| match kind.rem_euclid(4) {
+                3 => Move::Set(value.rem_euclid(dial.size)),
+                _ => moves_from(&[value])[0],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_any_dial_matches_naive() {
+        property::assert_same(
+            "day01 dial",
+            |rng| {
+                let dial = ((rng.range(0..=39), rng.range(0..=39)), (0..rng.range(0..=4)).map(|_| rng.range(0..=39)).collect());
+                let moves: Vec<(i64, i64)> = (0..rng.range(0..=20)).map(|_| (rng.range(0..=3), rng.range(-120..=120))).collect();
+                (dial, moves)
+            },
+            |(dial, moves)| {
+                let dial = dial_from(dial);
+                dial.count_passes(&mixed_moves(&dial, moves))
+            },
+            |(dial, moves)| {
+                let dial = dial_from(dial);
+                naive_passes(&dial, &mixed_moves(&dial, moves))
+            },
+        );
+    }
+
+    #[test]
+    fn test_dial_variants() {
+        let moves = parse_moves(["R5", "L12", "R30", "L3"]).unwrap();
+        let dial = Dial { size: 10, start: 0, targets: vec![0, 5] };
+        // Lands on 5, 3, 3 and 0; clicks through 5; 0 and 5; 0, 5 (three times each) and 0
+        assert_eq!(dial.count_landings(&moves), 2);
+        assert_eq!(dial.count_passes(&moves), 1 + 2 + 6 + 1);
+        assert_eq!(Dial { targets: Vec::new(), ..dial }.count_passes(&moves), 0);
+    }
+
+    #[test]
+    fn test_steps() {
+        let moves = parse_moves(["L68", "R48", "L218"]).unwrap();
+        let steps: Vec<Step> = Dial::default().steps(&moves).collect();
+        assert_eq!(
+            steps,
+            [
+                Step { movement: Move::Left(68), start: 50, end: 82, crossings: 1, landed: false },
+                Step { movement: Move::Right(48), start: 82, end: 30, crossings: 1, landed: false },
+                Step { movement: Move::Left(218), start: 30, end: 12, crossings: 2, landed: false },
+            ]
+        );
+
+        let trace = parse_rotations(["L50", "# skip", "R5"]).unwrap().trace();
+        assert_eq!(
+            trace.to_csv(),
+            "move,line,rotation,start,end,crossings,landed\n1,1,L50,50,0,1,yes\n2,3,R5,0,5,0,no\n"
+        );
+    }
+
+    #[test]
+    fn test_find_moves() {
+        let dial = Dial::default();
+        for landings in 0..6 {
+            for passes in landings..landings + 250 {
+                let moves = dial.find_moves(Some(landings), Some(passes), 10).unwrap();
+                let text: Vec<String> = moves.iter().map(Move::to_string).collect();
+                let parsed = parse_moves(&text).unwrap();
+                assert_eq!((part1(&parsed), part2(&parsed)), (landings, passes));
+            }
+        }
+        assert_eq!(dial.find_moves(Some(2), None, 2), Ok(vec![Move::Right(50), Move::Right(100)]));
+        assert_eq!(dial.find_moves(None, Some(3), 1), Ok(vec![Move::Right(349)]));
+        assert_eq!(dial.find_moves(Some(0), Some(0), 0), Ok(Vec::new()));
+
+        assert!(dial.find_moves(Some(3), Some(2), 10).is_err());
+        assert!(dial.find_moves(Some(4), Some(9), 3).unwrap_err().contains("budget"));
+        assert!(dial.find_moves(None, None, 3).is_err());
+        assert!(dial.find_moves(Some(1), Some(1 << 40), 3).unwrap_err().contains("longer"));
+        // Hopeless requests fail before building any move, and huge counts don't overflow
+        assert!(dial.find_moves(Some(1 << 40), None, 3).unwrap_err().contains("budget"));
+        assert!(dial.find_moves(Some(1), Some(i64::MAX), 3).unwrap_err().contains("longer"));
+        assert!(dial.find_moves(None, Some(i64::MAX), 3).unwrap_err().contains("longer"));
+        assert!(dial.find_moves(None, Some(1), 0).unwrap_err().contains("budget"));
+        assert!(Dial { targets: Vec::new(), ..Dial::default() }.find_moves(None, Some(1), 3).is_err());
+    }
+
+    #[test]
+    fn test_find_moves_on_any_dial() {
+        let mut rng = crate::rng::Rng::new(24);
+        for _ in 0..500 {
+            let size = rng.range(2..=30);
+            let mut targets: Vec<i64> = (0..rng.range(1..=4)).map(|_| rng.range(0..=size - 1)).collect();
+            targets.sort_unstable();
+            targets.dedup();
+            let dial = Dial { size, start: rng.range(0..=size - 1), targets };
+            let landings = rng.range(0..=5);
+            let passes = landings + rng.range(0..=100);
+            match dial.find_moves(Some(landings), Some(passes), 5) {
+                Ok(moves) => assert_eq!(naive_passes(&dial, &moves), passes, "{:?}", dial),
+                // Without landings every move has to stop off the targets, and when those
+                // spots are scarce some pass counts are out of reach
+                Err(error) => assert!(landings == 0 && dial.targets.len() > 1, "{:?} {} {}: {}", dial, landings, passes, error),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dial() {
+        let rotations = parse_rotations(["dial targets=90,0,90 size=360 start=0", "R90"]).unwrap();
+        assert_eq!(rotations.dial, Dial { size: 360, start: 0, targets: vec![0, 90] });
+        assert_eq!(rotations.moves, [Move::Right(90)]);
+        assert_eq!(parse_rotations(["dial", "L1"]).unwrap().dial, Dial::default());
+        assert_eq!(parse_rotations(["L1"]).unwrap().dial, Dial::default());
+
+        let error = |line: &str| parse_rotations([line, "R1"]).unwrap_err();
+        assert_eq!(error("dial start=7 size=5"), ParseError::new(1, 12, "a start position on the dial", "`7`"));
+        assert_eq!(error("dial targets=1,x"), ParseError::new(1, 16, "a target position", "`x`"));
+        assert_eq!(error("dial size=0"), ParseError::new(1, 11, "a dial size from 1 to 2^62", "`0`"));
+        assert_eq!(
+            error("dial size=4611686018427387905"),
+            ParseError::new(1, 11, "a dial size from 1 to 2^62", "`4611686018427387905`")
+        );
+
+        // The largest dial still turns its full distance range without overflowing
+        let largest = ["dial size=4611686018427387904 start=4611686018427387903", "R4294967295", "L4294967295"];
+        let rotations = parse_rotations(largest).unwrap();
+        assert_eq!(rotations.dial.size, MAX_DIAL_SIZE);
+        assert_eq!(rotations.dial.count_passes(&rotations.moves), 2);
+        assert_eq!(error("dial turns=3"), ParseError::new(1, 6, "`size=`, `start=` or `targets=`", "`turns=3`"));
+        assert_eq!(
+            parse_rotations(["L1", "dial size=5"]).unwrap_err(),
+            ParseError::new(2, 1, "`L`, `R` or `=`", "`d`")
+        );
     }
 }
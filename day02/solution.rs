@@ -1,199 +1,376 @@
-use std::collections::HashSet;
-use std::path::Path;
-
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
-
-fn part1(line: &str) -> i64 {
-    let ranges: Vec<(String, String)> = line
-        .split(',')
-        .map(str::trim)
-        .filter(|segment| !segment.is_empty())
-        .filter_map(|segment| {
-            segment
-                .split_once('-')
-                .map(|(start, end)| (start.trim().to_string(), end.trim().to_string()))
-        })
-        .collect();
+use std::fmt;
 
-    let mut total_sum: i64 = 0;
+use crate::rng::Rng;
+use crate::solution::{ParseError, Solution};
+use crate::utils::Pattern;
 
-    for (start, end) in ranges {
-        let start_value = start.parse::<i64>().unwrap();
-        let end_value = end.parse::<i64>().unwrap();
-        if start_value > end_value {
-            continue;
-        }
+const RANGE: Pattern = Pattern::new("{a range start}-{a range end}").named("a range `start-end`");
+
+/// Largest ID a range can reach, 2^80 - 1. Up to there one range's repeated IDs sum to less
+/// than 2^120 in any radix, so the `u128` totals have room for hundreds of the widest ranges.
+pub const MAX_ID: u128 = (1 << 80) - 1;
 
-        let min_len = digit_len(start_value);
-        let max_len = digit_len(end_value);
+/// Why [`parse_ranges_in`] rejected its input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangesError {
+    /// The radix is outside `2..=36`
+    Radix(u32),
+    Parse(ParseError),
+}
+
+impl fmt::Display for RangesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RangesError::Radix(radix) => write!(f, "radix must be between 2 and 36, got {}", radix),
+            RangesError::Parse(error) => error.fmt(f),
+        }
+    }
+}
 
-        for len in min_len..=max_len {
-            if len % 2 != 0 {
-                continue;
-            }
+impl std::error::Error for RangesError {}
 
-            let half_len = len / 2;
-            let pow_half = 10_i64.pow(half_len as u32);
-            let factor = pow_half + 1;
-            let half_min = pow_half / 10;
-            let half_max = pow_half - 1;
+impl From<ParseError> for RangesError {
+    fn from(error: ParseError) -> Self {
+        RangesError::Parse(error)
+    }
+}
 
-            let lower_bound = half_min.max((start_value + factor - 1) / factor);
-            let upper_bound = half_max.min(end_value / factor);
+pub fn parse_ranges(input: &str) -> Result<Vec<(u128, u128)>, ParseError> {
+    parse_digits(input, 10)
+}
 
-            if lower_bound > upper_bound {
-                continue;
-            }
+/// Like [`parse_ranges`] for IDs written in `radix`, which must be from 2 to 36
+pub fn parse_ranges_in(input: &str, radix: u32) -> Result<Vec<(u128, u128)>, RangesError> {
+    if !(2..=36).contains(&radix) {
+        return Err(RangesError::Radix(radix));
+    }
+    Ok(parse_digits(input, radix)?)
+}
 
-            for half in lower_bound..=upper_bound {
-                let candidate = half * factor;
-                if candidate >= start_value && candidate <= end_value {
-                    total_sum += candidate;
+fn parse_digits(input: &str, radix: u32) -> Result<Vec<(u128, u128)>, ParseError> {
+    let mut ranges = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        let line_no = idx + 1;
+        for segment in line.split(',').map(str::trim).filter(|segment| !segment.is_empty()) {
+            let [start, end] = RANGE.fields_in(line_no, line, segment)?;
+            let id = |text: &str, expected: &str| match u128::from_str_radix(text, radix) {
+                Ok(id) if id <= MAX_ID => Ok(id),
+                Err(err) if *err.kind() != std::num::IntErrorKind::PosOverflow => {
+                    Err(ParseError::at(line_no, line, text, expected))
                 }
-            }
+                _ => Err(ParseError::at(line_no, line, text, format!("{} below 2^80", expected))),
+            };
+            ranges.push((id(start, "a range start")?, id(end, "a range end")?));
         }
     }
 
-    total_sum
+    Ok(ranges)
 }
 
-fn part2(line: &str) -> i64 {
-    let ranges: Vec<(String, String)> = line
-        .split(',')
-        .map(str::trim)
-        .filter(|segment| !segment.is_empty())
-        .filter_map(|segment| {
-            segment
-                .split_once('-')
-                .map(|(start, end)| (start.trim().to_string(), end.trim().to_string()))
-        })
-        .collect();
+/// Which IDs count as invalid: a block of digits written exactly twice (part 1), or at least twice (part 2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeats {
+    Twice,
+    AtLeastTwice,
+}
+
+pub fn part1(ranges: &[(u128, u128)]) -> u128 {
+    sum_repeated(ranges, 10, Repeats::Twice)
+}
 
-    let mut total_sum: i64 = 0;
+pub fn part2(ranges: &[(u128, u128)]) -> u128 {
+    sum_repeated(ranges, 10, Repeats::AtLeastTwice)
+}
 
-    for (start, end) in ranges {
-        let start_value = start.parse::<i64>().unwrap();
-        let end_value = end.parse::<i64>().unwrap();
+/// Sum of the IDs in `ranges` whose digits in `radix` are one block repeated.
+///
+/// Works one digit length at a time with closed-form sums, so the cost doesn't depend on how
+/// wide the ranges are. With IDs up to [`MAX_ID`] the total only overflows for hundreds of
+/// the widest ranges, which panics. Panics if `radix` is outside `2..=36`.
+pub fn sum_repeated(ranges: &[(u128, u128)], radix: u32, repeats: Repeats) -> u128 {
+    assert!((2..=36).contains(&radix), "radix must be between 2 and 36, got {}", radix);
+    let radix = radix as u128;
+    let mut total_sum: u128 = 0;
+
+    for &(start_value, end_value) in ranges {
         if start_value > end_value {
             continue;
         }
 
-        let min_len = digit_len(start_value);
-        let max_len = digit_len(end_value);
-        let mut seen = HashSet::new();
+        for len in digit_len(start_value, radix)..=digit_len(end_value, radix) {
+            let low = start_value.max(radix.pow(len - 1));
+            let high = end_value.min(radix.pow(len) - 1);
 
-        for len in min_len..=max_len {
-            if len < 2 {
-                continue;
-            }
+            let sum = match repeats {
+                Repeats::Twice if len.is_multiple_of(2) => sum_with_period(low, high, radix, len, len / 2),
+                Repeats::Twice => 0,
+                Repeats::AtLeastTwice => sum_periodic(low, high, radix, len),
+            };
+            total_sum = total_sum.checked_add(sum).expect("sum of repeated IDs overflows u128");
+        }
+    }
 
-            let pow_len_minus_one = 10_i64.pow((len - 1) as u32);
-            let pow_len = 10_i64.pow(len as u32);
+    total_sum
+}
 
-            let range_start = start_value.max(pow_len_minus_one);
-            let range_end = end_value.min(pow_len - 1);
-            if range_start > range_end {
-                continue;
-            }
+fn digit_len(value: u128, radix: u128) -> u32 {
+    value.checked_ilog(radix).map_or(1, |log| log + 1)
+}
 
-            for block_len in 1..=len / 2 {
-                if len % block_len != 0 {
-                    continue;
-                }
+/// Sum of the `len`-digit IDs in `low..=high` made of a `period`-digit block repeated
+fn sum_with_period(low: u128, high: u128, radix: u128, len: u32, period: u32) -> u128 {
+    // Repeating a block multiplies it by 1, 0..01, 0..010..01 and so on
+    let factor = (1..len / period).fold(1, |factor, _| factor * radix.pow(period) + 1);
 
-                let repeat_count = len / block_len;
-                if repeat_count < 2 {
-                    continue;
-                }
+    // Blocks outside the `period`-digit range would land outside the `len`-digit bounds
+    let block_min = low.div_ceil(factor);
+    let block_max = high / factor;
+    if block_min > block_max {
+        return 0;
+    }
 
-                let pow_block = 10_i64.pow(block_len as u32);
-                let block_lower_limit = 10_i64.pow((block_len - 1) as u32);
-                let block_upper_limit = pow_block - 1;
+    let count = block_max - block_min + 1;
+    let block_sum = if count.is_multiple_of(2) {
+        count / 2 * (block_min + block_max)
+    } else {
+        (block_min + block_max) / 2 * count
+    };
+    block_sum * factor
+}
 
-                let mut factor = 0_i64;
-                for _ in 0..repeat_count {
-                    factor = factor
-                        .checked_mul(pow_block)
-                        .and_then(|v| v.checked_add(1))
-                        .expect("repeat factor overflow");
-                }
+/// Sum of the `len`-digit IDs in `low..=high` made of any block repeated at least twice
+fn sum_periodic(low: u128, high: u128, radix: u128, len: u32) -> u128 {
+    // Every period of an ID is a multiple of its smallest one, so going from small to large
+    // periods and subtracting the IDs a divisor already counted leaves each ID counted once
+    let mut smallest: Vec<(u32, u128)> = Vec::new();
+
+    for period in (1..len).filter(|period| len.is_multiple_of(*period)) {
+        let counted: u128 = smallest
+            .iter()
+            .filter(|(divisor, _)| period.is_multiple_of(*divisor))
+            .map(|(_, sum)| sum)
+            .sum();
+        smallest.push((period, sum_with_period(low, high, radix, len, period) - counted));
+    }
 
-                let block_min = block_lower_limit.max((range_start + factor - 1) / factor);
-                let block_max = block_upper_limit.min(range_end / factor);
+    smallest.iter().map(|(_, sum)| sum).sum()
+}
 
-                if block_min > block_max {
-                    continue;
-                }
+/// Random ID ranges of up to 10 digits on one comma-separated line, about 35 per unit
+/// of `scale`
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let ranges: Vec<String> = (0..35 * scale)
+        .map(|_| {
+            let digits = rng.range(1..=10) as u32;
+            let start = rng.range(10_i64.pow(digits - 1)..=10_i64.pow(digits) - 1);
+            let end = start + rng.range(0..=(start / 10).min(500_000));
+            format!("{}-{}", start, end)
+        })
+        .collect();
+    ranges.join(",") + "\n"
+}
 
-                for block in block_min..=block_max {
-                    if is_repetition(block) {
-                        continue;
-                    }
+pub struct Day02;
 
-                    let candidate = block * factor;
+impl Solution for Day02 {
+    const DAY: u8 = 2;
 
-                    if candidate >= range_start && candidate <= range_end && seen.insert(candidate)
-                    {
-                        total_sum += candidate;
-                    }
-                }
-            }
-        }
+    type Input = Vec<(u128, u128)>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_ranges(input)
     }
 
-    total_sum
-}
+    fn part1(input: &Self::Input) -> u128 {
+        part1(input)
+    }
 
-fn digit_len(value: i64) -> usize {
-    if value == 0 {
-        return 1;
+    fn part2(input: &Self::Input) -> Option<u128> {
+        Some(part2(input))
     }
 
-    (value.abs().ilog10() + 1) as usize
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
-fn is_repetition(value: i64) -> bool {
-    let s = value.to_string();
-    let bytes = s.as_bytes();
-    let len = bytes.len();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+    use crate::property;
+    use crate::rng::Rng;
 
-    for size in 1..=len / 2 {
-        if len % size != 0 {
-            continue;
-        }
+    #[test]
+    fn test_examples() {
+        examples::assert_examples::<Day02>(include_str!("README.md"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_ranges("11-22,95115"), Err(ParseError::new(1, 7, "a range `start-end`", "`95115`")));
+        assert_eq!(parse_ranges("11-22,\n95-1x5"), Err(ParseError::new(2, 4, "a range end", "`1x5`")));
+        assert_eq!(parse_ranges(" 11-22, 95-115,\n"), Ok(vec![(11, 22), (95, 115)]));
+    }
+
+    /// `(start, length)` pairs, so shrinking a start can never blow up the range
+    fn ranges_from(pairs: &[(i64, i64)]) -> Vec<(u128, u128)> {
+        pairs.iter().map(|&(start, length)| (start as u128, (start + length) as u128)).collect()
+    }
 
-        let pattern = &bytes[..size];
-        if bytes.chunks(size).all(|chunk| chunk == pattern) {
-            return true;
+    fn generate_ranges(rng: &mut Rng) -> Vec<(i64, i64)> {
+        (0..rng.range(1..=4))
+            .map(|_| {
+                // Start just below a repeated-digit ID about half the time, so ranges aren't mostly empty
+                let digits = rng.range(1..=5) as u32;
+                let block = rng.range(10_i64.pow(digits - 1)..=10_i64.pow(digits) - 1);
+                let repeated: i64 = block.to_string().repeat(rng.range(1..=3) as usize).parse().unwrap();
+                let start = if rng.chance(1, 2) { repeated } else { rng.range(0..=repeated) };
+                ((start - rng.range(0..=100)).max(0), rng.range(0..=300))
+            })
+            .collect()
+    }
+
+    /// Sum of every ID in the ranges whose digits in `radix` are invalid
+    fn naive_sum(ranges: &[(u128, u128)], radix: u32, invalid: impl Fn(&[u32]) -> bool) -> u128 {
+        ranges
+            .iter()
+            .flat_map(|&(start, end)| start..=end)
+            .filter(|&id| invalid(&digits(id, radix)))
+            .sum()
+    }
+
+    fn digits(mut id: u128, radix: u32) -> Vec<u32> {
+        let radix = radix as u128;
+        let mut digits = vec![(id % radix) as u32];
+        while id >= radix {
+            id /= radix;
+            digits.push((id % radix) as u32);
         }
+        digits
     }
 
-    false
-}
+    fn repeats(id: &[u32], times: usize) -> bool {
+        id.len().is_multiple_of(times) && id == id[..id.len() / times].repeat(times)
+    }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let line = utils::read_input(&input_path);
+    fn repeats_at_least_twice(id: &[u32]) -> bool {
+        (2..=id.len()).any(|times| repeats(id, times))
+    }
 
-    println!("Part 1: {}", part1(&line));
-    println!("Part 2: {}", part2(&line));
-}
+    #[test]
+    fn test_part1_matches_naive() {
+        property::assert_same(
+            "day02 part1",
+            generate_ranges,
+            |pairs| part1(&ranges_from(pairs)),
+            |pairs| naive_sum(&ranges_from(pairs), 10, |id| repeats(id, 2)),
+        );
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_part2_matches_naive() {
+        property::assert_same(
+            "day02 part2",
+            generate_ranges,
+            |pairs| part2(&ranges_from(pairs)),
+            |pairs| naive_sum(&ranges_from(pairs), 10, repeats_at_least_twice),
+        );
+    }
 
     #[test]
-    fn test_part1() {
-        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-        assert_eq!(part1(input), 1_227_775_554);
+    fn test_other_radixes_match_naive() {
+        let generate = |rng: &mut Rng| {
+            let ranges = (0..rng.range(1..=3)).map(|_| (rng.range(0..=100_000), rng.range(0..=2_000))).collect();
+            (rng.range(2..=36), ranges)
+        };
+        let radix = |radix: &i64| (*radix).clamp(2, 36) as u32;
+
+        property::assert_same(
+            "day02 twice in any radix",
+            generate,
+            |(r, pairs): &(i64, Vec<(i64, i64)>)| sum_repeated(&ranges_from(pairs), radix(r), Repeats::Twice),
+            |(r, pairs)| naive_sum(&ranges_from(pairs), radix(r), |id| repeats(id, 2)),
+        );
+        property::assert_same(
+            "day02 at least twice in any radix",
+            generate,
+            |(r, pairs): &(i64, Vec<(i64, i64)>)| sum_repeated(&ranges_from(pairs), radix(r), Repeats::AtLeastTwice),
+            |(r, pairs)| naive_sum(&ranges_from(pairs), radix(r), repeats_at_least_twice),
+        );
     }
 
     #[test]
-    fn test_part2() {
-        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-        assert_eq!(part2(input), 4_174_379_265);
+    fn test_parse_other_radixes() {
+        assert_eq!(
+            parse_ranges_in("ff-1FF,z-10", 16),
+            Err(RangesError::Parse(ParseError::new(1, 8, "a range start", "`z`")))
+        );
+        assert_eq!(parse_ranges_in("ff-1FF,z-10", 36), Ok(vec![(555, 1851), (35, 36)]));
+        assert_eq!(parse_ranges_in("1-2", 40), Err(RangesError::Radix(40)));
+        assert_eq!(parse_ranges_in("1-2", 1).unwrap_err().to_string(), "radix must be between 2 and 36, got 1");
+    }
+
+    #[test]
+    fn test_parse_wide_ranges() {
+        assert_eq!(
+            parse_ranges("18446744073709551615-18446744073709551616"),
+            Ok(vec![(u64::MAX as u128, u64::MAX as u128 + 1)])
+        );
+        assert_eq!(parse_ranges("1-1208925819614629174706175"), Ok(vec![(1, MAX_ID)]));
+        assert_eq!(
+            parse_ranges("1-1208925819614629174706176"),
+            Err(ParseError::new(1, 3, "a range end below 2^80", "`1208925819614629174706176`"))
+        );
+        assert_eq!(
+            parse_ranges_in("1-1ffffffffffffffffffffffffffffffff", 16),
+            Err(RangesError::Parse(ParseError::new(1, 3, "a range end below 2^80", "`1ffffffffffffffffffffffffffffffff`")))
+        );
+    }
+
+    #[test]
+    fn test_ranges_past_u64_max() {
+        let past_u64 = u64::MAX as u128 + 1;
+        for radix in [2, 10, 16, 36] {
+            let wide = [(past_u64 - 25_000, past_u64 + 25_000), (MAX_ID - 50_000, MAX_ID)];
+            assert_eq!(
+                sum_repeated(&wide, radix, Repeats::AtLeastTwice),
+                naive_sum(&wide, radix, repeats_at_least_twice),
+                "radix {}",
+                radix
+            );
+        }
+        // The widest range fits with room to spare in every radix
+        for radix in 2..=36 {
+            assert!(sum_repeated(&[(0, MAX_ID)], radix, Repeats::AtLeastTwice) < 1 << 120);
+        }
+    }
+
+    #[test]
+    fn test_ranges_near_u64_max() {
+        for radix in [2, 10, 16, 36] {
+            let u64_max = u64::MAX as u128;
+            let i64_max = i64::MAX as u128;
+            let near_max = [(u64_max - 200_000, u64_max), (i64_max - 100_000, i64_max + 100_000)];
+            assert_eq!(
+                sum_repeated(&near_max, radix, Repeats::AtLeastTwice),
+                naive_sum(&near_max, radix, repeats_at_least_twice),
+                "radix {}",
+                radix
+            );
+        }
+
+        // 1111111111111111111111111111111111111111111111111111111111111111 in binary
+        assert_eq!(sum_repeated(&[(u64::MAX as u128, u64::MAX as u128)], 2, Repeats::AtLeastTwice), u64::MAX as u128);
+        // Splitting the widest range anywhere must not change the total
+        for repeats in [Repeats::Twice, Repeats::AtLeastTwice] {
+            let whole = sum_repeated(&[(0, MAX_ID)], 10, repeats);
+            assert!(whole > u64::MAX as u128);
+            let split = [(0, 5_000_000_000), (5_000_000_001, u64::MAX as u128), (u64::MAX as u128 + 1, MAX_ID)];
+            assert_eq!(sum_repeated(&split, 10, repeats), whole);
+        }
     }
 }
@@ -1,27 +1,52 @@
-use std::path::Path;
+use crate::rng::Rng;
+use crate::solution::{ParseError, Solution};
 
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
+/// Batteries turned on in each bank for part 2, and so the fewest a bank can hold
+pub const PART2_BATTERIES: usize = 12;
 
-fn part1(lines: &[String]) -> i64 {
-    solve_batteries(lines, 2)
+pub fn parse_banks<I>(lines: I) -> Result<Vec<Vec<i64>>, ParseError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let line = line.as_ref();
+            if line.is_empty() {
+                return Err(ParseError::at(idx + 1, line, line, "a battery digit"));
+            }
+            let bank: Vec<i64> = line
+                .char_indices()
+                .map(|(col, c)| {
+                    c.to_digit(10)
+                        .map(i64::from)
+                        .ok_or_else(|| ParseError::at(idx + 1, line, &line[col..col + c.len_utf8()], "a battery digit"))
+                })
+                .collect::<Result<_, _>>()?;
+            // Part 2 turns on this many batteries in every bank
+            if bank.len() < PART2_BATTERIES {
+                let expected = format!("at least {} battery digits", PART2_BATTERIES);
+                return Err(ParseError::at(idx + 1, line, &line[line.len()..], expected));
+            }
+            Ok(bank)
+        })
+        .collect()
+}
+
+pub fn part1(banks: &[Vec<i64>]) -> i64 {
+    solve_batteries(banks, 2)
 }
 
-fn part2(lines: &[String]) -> i64 {
-    solve_batteries(lines, 12)
+pub fn part2(banks: &[Vec<i64>]) -> i64 {
+    solve_batteries(banks, PART2_BATTERIES)
 }
 
-fn solve_batteries(lines: &[String], target_count: usize) -> i64 {
+fn solve_batteries(banks: &[Vec<i64>], target_count: usize) -> i64 {
     let mut max_total = 0;
 
-    for line in lines {
-        let numbers: Vec<i64> = line
-            .chars()
-            .filter(|c| c.is_ascii_digit())
-            .map(|c| c.to_digit(10).unwrap() as i64)
-            .collect();
-        
+    for numbers in banks {
         let n = numbers.len();
         let mut dp: Vec<Vec<Option<Vec<i64>>>> = vec![vec![None; target_count + 1]; n + 1];
         dp[0][0] = Some(vec![]);
@@ -35,10 +60,10 @@ fn solve_batteries(lines: &[String], target_count: usize) -> i64 {
                 let curr_sequence = dp[i][j].as_ref().unwrap().clone();
                 
                 // Option 1: Don't take this battery
-                if i + 1 < n || j == target_count {
-                    if dp[i + 1][j].is_none() || is_better(&curr_sequence, dp[i + 1][j].as_ref().unwrap()) {
-                        dp[i + 1][j] = Some(curr_sequence.clone());
-                    }
+                if (i + 1 < n || j == target_count)
+                    && (dp[i + 1][j].is_none() || is_better(&curr_sequence, dp[i + 1][j].as_ref().unwrap()))
+                {
+                    dp[i + 1][j] = Some(curr_sequence.clone());
                 }
                 
                 // Option 2: Take this battery
@@ -74,51 +99,75 @@ fn is_better(a: &[i64], b: &[i64]) -> bool {
     a.len() > b.len()
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let lines = utils::read_lines(&input_path);
+/// Random banks of 100 batteries rated 1-9, 200 per unit of `scale`
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..200 * scale {
+        input.extend((0..100).map(|_| char::from(b'1' + rng.below(9) as u8)));
+        input.push('\n');
+    }
+    input
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_banks(input.lines())
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(part2(input))
+    }
 
-    println!("Part 1: {}", part1(&lines));
-    println!("Part 2: {}", part2(&lines));
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
 
     #[test]
-    fn test_part1() {
-        let input = vec![
-            "987654321111111".to_string(),
-            "811111111111119".to_string(),
-            "234234234234278".to_string(),
-            "818181911112111".to_string(),
-        ];
-        assert_eq!(part1(&input), 357);
-    }
-
-    #[test]
-    fn test_part2() {
-        let input = vec![
-            "987654321111111".to_string(),
-            "811111111111119".to_string(),
-            "234234234234278".to_string(),
-            "818181911112111".to_string(),
-        ];
-        assert_eq!(part2(&input), 3121910778619);
+    fn test_examples() {
+        examples::assert_examples::<Day03>(include_str!("README.md"));
     }
 
     #[test]
     fn test_single_line() {
         let input = vec!["987654321111791".to_string()];
-        assert_eq!(part1(&input), 99);
-        assert_eq!(part2(&input), 987654321791);
+        assert_eq!(part1(&parse_banks(&input).unwrap()), 99);
+        assert_eq!(part2(&parse_banks(&input).unwrap()), 987654321791);
     }
 
     #[test]
     fn test_empty_input() {
-        let input = vec![];
-        assert_eq!(part1(&input), 0);
-        assert_eq!(part2(&input), 0);
+        let input: Vec<String> = vec![];
+        assert_eq!(part1(&parse_banks(&input).unwrap()), 0);
+        assert_eq!(part2(&parse_banks(&input).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = vec!["987654321111".to_string(), "81x9".to_string()];
+        assert_eq!(parse_banks(&input), Err(ParseError::new(2, 3, "a battery digit", "`x`")));
+
+        let input = vec!["987654321111".to_string(), "987".to_string()];
+        assert_eq!(parse_banks(&input), Err(ParseError::new(2, 4, "at least 12 battery digits", "end of line")));
+
+        let input = vec!["".to_string()];
+        assert_eq!(parse_banks(&input), Err(ParseError::new(1, 1, "a battery digit", "end of line")));
     }
 }
@@ -1,55 +1,36 @@
-use std::{path::Path};
+use crate::rng::Rng;
+use crate::solution::{ParseError, Solution};
+use crate::utils::{self, Grid};
 
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
-
-fn directions() -> Vec<(i64, i64)> {
-    vec![(0, 1), (1, 0), (0, -1), (-1, 0), (1, 1), (1, -1), (-1, 1), (-1, -1)]
-}
-
-fn move_rolls(grid: &mut [Vec<char>]) -> i64 {
-    
+fn move_rolls(grid: &mut Grid<char>) -> i64 {
     let mut total = 0;
     let mut positions_to_clear = Vec::new();
-    
-    for (row_idx, row) in grid.iter().enumerate() {
-        for (col_idx, &cell) in row.iter().enumerate() {
-            if cell == '@' {
-                let mut neighbor_count = 0;
-                for dir in directions() {
-                    let new_row = row_idx as i64 + dir.0;
-                    let new_col = col_idx as i64 + dir.1;
-                    if new_row >= 0 && new_col >= 0 
-                        && (new_row as usize) < grid.len() 
-                        && (new_col as usize) < grid[0].len()
-                        && grid[new_row as usize][new_col as usize] == '@' {
-                        neighbor_count += 1;
-                    }
-                }
-                if neighbor_count < 4 {
-                    positions_to_clear.push((row_idx, col_idx));
-                    total += 1;
-                }
+
+    for ((row, col), &cell) in grid.iter() {
+        if cell == '@' {
+            let neighbor_count = grid
+                .neighbors8(row, col)
+                .filter(|&position| grid[position] == '@')
+                .count();
+            if neighbor_count < 4 {
+                positions_to_clear.push((row, col));
+                total += 1;
             }
         }
     }
-    
-    for (row_idx, col_idx) in positions_to_clear {
 
-        grid[row_idx][col_idx] = '.';
+    for position in positions_to_clear {
+        grid[position] = '.';
     }
 
     total
 }
 
-fn part1(grid: &mut [Vec<char>]) -> i64 {
-
-    
+pub fn part1(grid: &mut Grid<char>) -> i64 {
     move_rolls(grid)
 }
 
-fn part2(grid: &mut [Vec<char>]) -> i64 {
+pub fn part2(grid: &mut Grid<char>) -> i64 {
     let mut total = 0;
 
     loop {
@@ -62,85 +43,96 @@ fn part2(grid: &mut [Vec<char>]) -> i64 {
     total
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let mut grid = utils::read_grid(&input_path);
+/// Random square grid of paper rolls, its area `scale` times that of a real 137x137 one
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let side = (137.0 * (scale as f64).sqrt()) as usize;
+    let mut input = String::with_capacity(side * (side + 1));
+    for _ in 0..side {
+        input.extend((0..side).map(|_| if rng.chance(3, 5) { '@' } else { '.' }));
+        input.push('\n');
+    }
+    input
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Grid<char>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        utils::parse_grid(input, ".@")
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        part1(&mut input.clone())
+    }
 
-    println!("Part 1: {}", part1(&mut grid));
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(part2(&mut input.clone()))
+    }
 
-    let mut grid = utils::read_grid(&input_path);
-    println!("Part 2: {}", part2(&mut grid));
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
 
     #[test]
-    fn test_part1_example() {
-        let mut input = vec![
-            vec!['.', '.', '@', '@', '.', '@', '@', '@', '@', '.'],
-            vec!['@', '@', '@', '.', '@', '.', '@', '.', '@', '@'],
-            vec!['@', '@', '@', '@', '@', '.', '@', '.', '@', '@'],
-            vec!['@', '.', '@', '@', '@', '@', '.', '.', '@', '.'],
-            vec!['@', '@', '.', '@', '@', '@', '@', '.', '@', '@'],
-            vec!['.', '@', '@', '@', '@', '@', '@', '@', '.', '@'],
-            vec!['.', '@', '.', '@', '.', '@', '.', '@', '@', '@'],
-            vec!['@', '.', '@', '@', '@', '.', '@', '@', '@', '@'],
-            vec!['.', '@', '@', '@', '@', '@', '@', '@', '@', '.'],
-            vec!['@', '.', '@', '.', '@', '@', '@', '.', '@', '.'],
-        ];
-        assert_eq!(part1(&mut input), 13);
-    }
-
-    #[test]
-    fn test_part2_example() {
-        let mut input = vec![
-            vec!['.', '.', '@', '@', '.', '@', '@', '@', '@', '.'],
-            vec!['@', '@', '@', '.', '@', '.', '@', '.', '@', '@'],
-            vec!['@', '@', '@', '@', '@', '.', '@', '.', '@', '@'],
-            vec!['@', '.', '@', '@', '@', '@', '.', '.', '@', '.'],
-            vec!['@', '@', '.', '@', '@', '@', '@', '.', '@', '@'],
-            vec!['.', '@', '@', '@', '@', '@', '@', '@', '.', '@'],
-            vec!['.', '@', '.', '@', '.', '@', '.', '@', '@', '@'],
-            vec!['@', '.', '@', '@', '@', '.', '@', '@', '@', '@'],
-            vec!['.', '@', '@', '@', '@', '@', '@', '@', '@', '.'],
-            vec!['@', '.', '@', '.', '@', '@', '@', '.', '@', '.'],
-        ];
-        assert_eq!(part2(&mut input), 43);
+    fn test_examples() {
+        examples::assert_examples::<Day04>(include_str!("README.md"));
     }
 
     #[test]
     fn test_part1_single_roll() {
-        let mut input = vec![
+        let mut input = Grid::from_rows(vec![
             vec!['.', '.', '.'],
             vec!['.', '@', '.'],
             vec!['.', '.', '.'],
-        ];
+        ]);
         assert_eq!(part1(&mut input), 1);
     }
 
     #[test]
     fn test_part1_four_neighbors() {
-        let mut input = vec![
+        let mut input = Grid::from_rows(vec![
             vec!['.', '@', '.'],
             vec!['@', '@', '@'],
             vec!['.', '@', '.'],
-        ];
+        ]);
         // Center has 4 neighbors, so it won't be counted
         assert_eq!(part1(&mut input), 4);
     }
 
     #[test]
     fn test_part2_iterative_removal() {
-        let mut input = vec![
+        let mut input = Grid::from_rows(vec![
             vec!['.', '.', '.', '.', '.'],
             vec!['.', '@', '@', '@', '.'],
             vec!['.', '@', '@', '@', '.'],
             vec!['.', '@', '@', '@', '.'],
             vec!['.', '.', '.', '.', '.'],
-        ];
+        ]);
         // All rolls should be removable eventually
         assert_eq!(part2(&mut input), 9);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Day04::parse("..@\n.#@\n"),
+            Err(ParseError::new(2, 2, "`.` or `@`", "`#`"))
+        );
+        assert_eq!(
+            Day04::parse("..@\n.@\n"),
+            Err(ParseError::new(2, 3, "a row of 3 cells", "2 cells"))
+        );
+    }
 }
@@ -1,17 +1,37 @@
-use std::{collections::VecDeque, path::Path};
+use std::collections::VecDeque;
+use std::fmt::Write;
 
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
+use crate::rng::Rng;
+use crate::solution::{parse_token, ParseError, Solution};
+use crate::utils::{self, Pattern};
 
-fn parse_ranges(iter: &mut std::slice::Iter<String>) -> Vec<(i64, i64)> {
+const RANGE: Pattern = Pattern::new("{a range start}-{a range end}").named("a range `start-end`");
+
+pub struct Inventory {
+    ranges: Vec<(i64, i64)>,
+    numbers: VecDeque<i64>,
+}
+
+pub fn parse_inventory<I>(lines: I) -> Result<Inventory, ParseError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
     let mut ranges = Vec::new();
-    for line in iter.by_ref() {
-        if line.is_empty() { break; }
-        let (a, b) = line.split_once('-').unwrap();
-        ranges.push((a.parse::<i64>().unwrap(), b.parse::<i64>().unwrap()));
+    let mut numbers = Vec::new();
+
+    // Ranges come first; every later block holds ingredient IDs
+    for (section, line_no, line) in utils::section_lines(lines) {
+        let line = line.as_ref();
+        if section == 0 {
+            ranges.push(RANGE.parse(line_no, line)?);
+        } else {
+            numbers.push(parse_token(line_no, line, line, "an ingredient ID")?);
+        }
     }
-    ranges
+
+    numbers.sort();
+    Ok(Inventory { ranges, numbers: numbers.into_iter().collect() })
 }
 
 fn merge_ranges(ranges: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
@@ -36,22 +56,9 @@ fn merge_ranges(ranges: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
     merged
 }
 
-fn parse_numbers(iter: &mut std::slice::Iter<String>) -> VecDeque<i64> {
-    let mut numbers: Vec<i64> = iter
-        .filter(|line| !line.is_empty())
-        .map(|line| line.parse().unwrap())
-        .collect();
-    
-    numbers.sort();
-    numbers.into_iter().collect()
-}
-
-fn part1(lines: &[String]) -> i64 {
-    let mut iter = lines.iter();
-    
-    let ranges = parse_ranges(&mut iter);
-    let merged = merge_ranges(ranges);
-    let mut numbers = parse_numbers(&mut iter);
+pub fn part1(inventory: &Inventory) -> i64 {
+    let merged = merge_ranges(inventory.ranges.clone());
+    let mut numbers = inventory.numbers.clone();
 
     let mut count: i64 = 0;
     for (start, end) in merged {
@@ -70,12 +77,8 @@ fn part1(lines: &[String]) -> i64 {
     count
 }
 
-fn part2(lines: &[String]) -> i64 {
-    let mut iter = lines.iter();
-    
-    let ranges = parse_ranges(&mut iter);
-    let merged = merge_ranges(ranges);
-    let _numbers = parse_numbers(&mut iter);
+pub fn part2(inventory: &Inventory) -> i64 {
+    let merged = merge_ranges(inventory.ranges.clone());
 
     let mut total_fresh: i64 = 0;
     for (start, end) in merged {
@@ -85,55 +88,59 @@ fn part2(lines: &[String]) -> i64 {
     total_fresh
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let lines = utils::read_lines(&input_path);
+/// Random overlapping fresh ranges and ingredient IDs, about 180 ranges and 1000 IDs
+/// per unit of `scale`. Ranges narrow as their number grows, so a similar share of IDs
+/// stays fresh.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    const MAX_ID: i64 = 500_000_000_000_000;
+    let mut input = String::new();
+    for _ in 0..180 * scale {
+        let start = rng.range(1..=MAX_ID);
+        writeln!(input, "{}-{}", start, start + rng.range(0..=MAX_ID / 100 / scale as i64)).unwrap();
+    }
+    input.push('\n');
+    for _ in 0..1000 * scale {
+        writeln!(input, "{}", rng.range(1..=MAX_ID)).unwrap();
+    }
+    input
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Inventory;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_inventory(input.lines())
+    }
 
-    println!("Part 1: {}", part1(&lines));
-    println!("Part 2: {}", part2(&lines));
+    fn part1(input: &Self::Input) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+    use crate::property;
+    use crate::rng::Rng;
 
     #[test]
-    fn test_part1_example() {
-        let input = vec![
-            "3-5".to_string(),
-            "10-14".to_string(),
-            "16-20".to_string(),
-            "12-18".to_string(),
-            "".to_string(),
-            "1".to_string(),
-            "5".to_string(),
-            "8".to_string(),
-            "11".to_string(),
-            "17".to_string(),
-            "32".to_string(),
-        ];
-        // Numbers 5, 11, and 17 are in the merged ranges
-        assert_eq!(part1(&input), 3);
-    }
-
-    #[test]
-    fn test_part2_example() {
-        let input = vec![
-            "3-5".to_string(),
-            "10-14".to_string(),
-            "16-20".to_string(),
-            "12-18".to_string(),
-            "".to_string(),
-            "1".to_string(),
-            "5".to_string(),
-            "8".to_string(),
-            "11".to_string(),
-            "17".to_string(),
-            "32".to_string(),
-        ];
-        // Merged ranges: 3-5 (3), 10-20 (11)
-        // Total fresh = 3 + 11 = 14
-        assert_eq!(part2(&input), 14);
+    fn test_examples() {
+        examples::assert_examples::<Day05>(include_str!("README.md"));
     }
 
     #[test]
@@ -165,7 +172,7 @@ mod tests {
             "10".to_string(),
             "20".to_string(),
         ];
-        assert_eq!(part1(&input), 0);
+        assert_eq!(part1(&parse_inventory(&input).unwrap()), 0);
     }
 
     #[test]
@@ -177,6 +184,74 @@ mod tests {
             "10".to_string(),
             "50".to_string(),
         ];
-        assert_eq!(part1(&input), 3);
+        assert_eq!(part1(&parse_inventory(&input).unwrap()), 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_errors() {
+        let input = vec!["3-5".to_string(), "10-x14".to_string()];
+        assert_eq!(
+            parse_inventory(&input).err(),
+            Some(ParseError::new(2, 4, "a range end", "`x14`"))
+        );
+
+        let input = vec!["3-5".to_string(), "".to_string(), "1".to_string(), "x".to_string()];
+        assert_eq!(
+            parse_inventory(&input).err(),
+            Some(ParseError::new(4, 1, "an ingredient ID", "`x`"))
+        );
+    }
+
+    #[test]
+    fn test_parse_streamed_lines() {
+        let inventory = parse_inventory("3-5\n10-14\n\n\n1\n5\n\n11\n".lines()).unwrap();
+        assert_eq!(inventory.ranges, [(3, 5), (10, 14)]);
+        assert_eq!(inventory.numbers, [1, 5, 11]);
+    }
+
+    /// `(start, length)` range pairs and ingredient IDs, built into an inventory as parsing would
+    type Case = (Vec<(i64, i64)>, Vec<i64>);
+
+    fn inventory_from((pairs, ids): &Case) -> Inventory {
+        let mut numbers = ids.clone();
+        numbers.sort();
+        Inventory {
+            ranges: pairs.iter().map(|&(start, length)| (start, start + length)).collect(),
+            numbers: numbers.into(),
+        }
+    }
+
+    fn generate_case(rng: &mut Rng) -> Case {
+        let pairs = (0..rng.range(0..=8)).map(|_| (rng.range(0..=200), rng.range(0..=40))).collect();
+        let ids = (0..rng.range(0..=20)).map(|_| rng.range(0..=250)).collect();
+        (pairs, ids)
+    }
+
+    #[test]
+    fn test_part1_matches_naive() {
+        property::assert_same(
+            "day05 part1",
+            generate_case,
+            |case| part1(&inventory_from(case)),
+            |case| {
+                let inventory = inventory_from(case);
+                let fresh = |id: i64| inventory.ranges.iter().any(|&(start, end)| (start..=end).contains(&id));
+                inventory.numbers.iter().filter(|&&id| fresh(id)).count() as i64
+            },
+        );
+    }
+
+    #[test]
+    fn test_part2_matches_naive() {
+        property::assert_same(
+            "day05 part2",
+            generate_case,
+            |case| part2(&inventory_from(case)),
+            |case| {
+                let ids: std::collections::HashSet<i64> =
+                    inventory_from(case).ranges.iter().flat_map(|&(start, end)| start..=end).collect();
+                ids.len() as i64
+            },
+        );
+    }
+}
@@ -1,8 +1,29 @@
-use std::path::Path;
+use std::fmt::Write;
 
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
+use crate::rng::Rng;
+use crate::solution::{ParseError, Solution};
+use crate::utils;
+
+/// Check that every row holds only digits and spaces, and the last row only operators
+pub fn parse_worksheet(input: &str) -> Result<Vec<String>, ParseError> {
+    let lines = utils::parse_lines(input);
+    let ops_idx = lines.len().saturating_sub(1);
+
+    for (idx, line) in lines.iter().enumerate() {
+        let (allowed, expected) = if idx == ops_idx {
+            ("*+ ", "`*` or `+`")
+        } else {
+            ("0123456789 ", "a digit")
+        };
+        if let Some((col, c)) = line.char_indices().find(|&(_, c)| !allowed.contains(c)) {
+            return Err(ParseError::at(idx + 1, line, &line[col..col + c.len_utf8()], expected));
+        }
+        // Numbers too long for an i64 are reported here rather than when solving
+        utils::extract_ints::<i64>(idx + 1, line)?;
+    }
+
+    Ok(lines)
+}
 
 fn parse_operations(line: &str) -> Vec<char> {
     line.split_whitespace()
@@ -14,11 +35,8 @@ fn parse_numbers(lines: &[String]) -> Vec<Vec<i64>> {
     // Parse each line into a vector of numbers, filtering out lines with no numbers
     let rows: Vec<Vec<i64>> = lines
         .iter()
-        .map(|line| {
-            line.split_whitespace()
-                .filter_map(|s| s.parse::<i64>().ok())
-                .collect()
-        })
+        .enumerate()
+        .map(|(idx, line)| utils::extract_ints::<i64>(idx + 1, line).expect("numbers are checked by parse_worksheet"))
         .filter(|row: &Vec<i64>| !row.is_empty())
         .collect();
     
@@ -56,12 +74,12 @@ fn parse_columns_by_vertical_position(lines: &[String]) -> (Vec<Vec<i64>>, Vec<c
             });
         
         let op_char = ops_line.chars().nth(pos);
-        let is_operator = op_char.map_or(false, |c| c == '*' || c == '+');
+        let is_operator = op_char.is_some_and(|c| c == '*' || c == '+');
         
         if is_operator {
-            if current_op.is_some() {
+            if let Some(op) = current_op {
                 columns.push(current_group.clone());
-                operations.push(current_op.unwrap());
+                operations.push(op);
                 current_group.clear();
             }
             current_op = op_char;
@@ -94,7 +112,7 @@ fn apply_operations(columns: &[Vec<i64>], operations: &[char]) -> i64 {
     result
 }
 
-fn part1(lines: &[String]) -> i64 {
+pub fn part1(lines: &[String]) -> i64 {
     if lines.is_empty() {
         return 0;
     }
@@ -103,42 +121,89 @@ fn part1(lines: &[String]) -> i64 {
     apply_operations(&columns, &operations)
 }
 
-fn part2(lines: &[String]) -> i64 {
+pub fn part2(lines: &[String]) -> i64 {
     let (columns, operations) = parse_columns_by_vertical_position(lines);
     apply_operations(&columns, &operations)
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let lines = utils::read_lines(&input_path);
+/// Random worksheet of 1000 problems per unit of `scale`, four rows of numbers each.
+/// Numbers keep to three digits so the grand totals stay within `i64` at any scale.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut rows = vec![String::new(); 5];
+    for problem in 0..1000 * scale {
+        if problem > 0 {
+            rows.iter_mut().for_each(|row| row.push(' '));
+        }
+        let numbers: Vec<String> = (0..4)
+            .map(|_| {
+                let digits = rng.range(1..=3) as u32;
+                rng.range(10_i64.pow(digits - 1)..=10_i64.pow(digits) - 1).to_string()
+            })
+            .collect();
+        let width = numbers.iter().map(String::len).max().unwrap();
+        let align_right = rng.chance(1, 2);
+        for (row, number) in rows.iter_mut().zip(&numbers) {
+            if align_right {
+                write!(row, "{:>width$}", number).unwrap();
+            } else {
+                write!(row, "{:<width$}", number).unwrap();
+            }
+        }
+        let operation = if rng.chance(1, 2) { '*' } else { '+' };
+        write!(rows[4], "{:<width$}", operation).unwrap();
+    }
+    rows.join("\n") + "\n"
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = Vec<String>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_worksheet(input)
+    }
 
-    println!("Part 1: {}", part1(&lines));
-    println!("Part 2: {}", part2(&lines));
+    fn part1(input: &Self::Input) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
 
     #[test]
-    fn test_part1() {
-        let input = vec![
-            "123 328  51 64 ".to_string(),
-            " 45 64  387 23 ".to_string(),
-            "  6 98  215 314".to_string(),
-            "*   +   *   +  ".to_string(),
-        ];
-        assert_eq!(part1(&input), 4277556);
+    fn test_examples() {
+        examples::assert_examples::<Day06>(include_str!("README.md"));
     }
 
     #[test]
-    fn test_part2() {
-        let input = vec![
-            "123 328  51 64 ".to_string(),
-            " 45 64  387 23 ".to_string(),
-            "  6 98  215 314".to_string(),
-            "*   +   *   +  ".to_string(),
-        ];
-        assert_eq!(part2(&input), 3263827);
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_worksheet("12 3\n4x 5\n*  +\n"),
+            Err(ParseError::new(2, 2, "a digit", "`x`"))
+        );
+        assert_eq!(
+            parse_worksheet("12 3\n4  5\n*  -\n"),
+            Err(ParseError::new(3, 4, "`*` or `+`", "`-`"))
+        );
+        assert_eq!(
+            parse_worksheet("1 99999999999999999999\n2 3\n*  +\n"),
+            Err(ParseError::new(1, 3, "an integer", "`99999999999999999999`"))
+        );
     }
 }
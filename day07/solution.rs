@@ -1,20 +1,12 @@
 use std::collections::HashMap;
-use std::path::Path;
 
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
+use crate::rng::Rng;
+use crate::solution::{ParseError, Solution};
+use crate::utils::{self, Grid};
 
-fn find_start_position(grid: &[Vec<char>]) -> Option<usize> {
-    if grid.is_empty() {
-        return None;
-    }
-    grid[0].iter().position(|&c| c == 'S')
-}
-
-fn move_down(count: &mut i64, grid: &mut [Vec<char>], row: usize, col: usize, visited: &mut HashMap<(usize, usize), bool>) {
+fn move_down(count: &mut i64, grid: &mut Grid<char>, row: usize, col: usize, visited: &mut HashMap<(usize, usize), bool>) {
     let next_row = row + 1;
-    if next_row >= grid.len() {
+    if next_row >= grid.height() {
         return;
     }
 
@@ -24,133 +16,188 @@ fn move_down(count: &mut i64, grid: &mut [Vec<char>], row: usize, col: usize, vi
 
     visited.insert((next_row, col), true);
     
-    if grid[next_row][col] == '^' {
+    if grid[(next_row, col)] == '^' {
         *count += 1;
         if col > 0 {
-            grid[next_row][col - 1] = '|';
+            grid[(next_row, col - 1)] = '|';
             move_down(count, grid, next_row, col - 1, visited);
         }
-        if col + 1 < grid[next_row].len() {
-            grid[next_row][col + 1] = '|';
+        if col + 1 < grid.width() {
+            grid[(next_row, col + 1)] = '|';
             move_down(count, grid, next_row, col + 1, visited);
         }
     } else {
-        grid[next_row][col] = '|';
+        grid[(next_row, col)] = '|';
         move_down(count, grid, next_row, col, visited);
     }
 }
 
-fn part1(grid: &[Vec<char>]) -> i64 {
-    if let Some(pos) = find_start_position(grid) {
+pub fn part1(grid: &Grid<char>) -> i64 {
+    if let Some((row, col)) = grid.find(|&c| c == 'S') {
         let mut count = 0;
-        let mut grid_copy = grid.to_vec();
+        let mut grid_copy = grid.clone();
         let mut visited = HashMap::new();
 
-        move_down(&mut count, &mut grid_copy, 0, pos, &mut visited);        
+        move_down(&mut count, &mut grid_copy, row, col, &mut visited);
         count
     } else {
         0
     }
 }
 
-fn part2(grid: &[Vec<char>]) -> i64 {
-    if grid.is_empty() {
-        return 0;
-    }
-    
-    let start_pos = match find_start_position(grid) {
+pub fn part2(grid: &Grid<char>) -> i64 {
+    let (start_row, start_col) = match grid.find(|&c| c == 'S') {
         Some(pos) => pos,
         None => return 0,
     };
-    
-    let rows = grid.len();
-    let cols = grid[0].len();
-    
-    let mut dp = vec![vec![0i64; cols]; rows + 1];
-    
-    for col in 0..cols {
-        dp[rows][col] = 1;
-    }
-    
+
+    let rows = grid.height();
+    let cols = grid.width();
+
+    // Paths from each cell to the bottom; the extra last row is past the manifold
+    let mut dp = Grid::filled(cols, rows + 1, 0i64);
+
+    dp.row_mut(rows).fill(1);
+
     for row in (0..rows).rev() {
         for col in 0..cols {
-            if grid[row][col] == '^' {
+            if grid[(row, col)] == '^' {
                 if col > 0 {
-                    dp[row][col] += dp[row + 1][col - 1];
+                    dp[(row, col)] += dp[(row + 1, col - 1)];
                 }
                 if col + 1 < cols {
-                    dp[row][col] += dp[row + 1][col + 1];
+                    dp[(row, col)] += dp[(row + 1, col + 1)];
                 }
             } else {
-                dp[row][col] = dp[row + 1][col];
+                dp[(row, col)] = dp[(row + 1, col)];
             }
         }
     }
-    
-    dp[0][start_pos]
+
+    dp[(start_row, start_col)]
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let grid = utils::read_grid(&input_path);
+/// Random manifold with `scale` times the area of a real 141x142 one. Splitters sit on
+/// alternate rows inside the triangle that beams can reach from `S`, never side by side.
+/// They thin out on taller manifolds so a beam meets about as many splitters on its way
+/// down as in a real input, which keeps the number of timelines within `i64`.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    const SPLITS_PER_BEAM: f64 = 30.0;
+    let factor = (scale as f64).sqrt();
+    let width = (141.0 * factor) as usize | 1;
+    let height = (142.0 * factor) as usize;
+    let center = width / 2;
+    let density = (SPLITS_PER_BEAM / (height / 2) as f64).min(0.5);
+
+    let mut input = String::with_capacity(height * (width + 1));
+    for row in 0..height {
+        let mut previous = '.';
+        for col in 0..width {
+            let reachable = row % 2 == 0 && col.abs_diff(center) < row / 2;
+            let cell = match () {
+                _ if row == 0 && col == center => 'S',
+                _ if reachable && previous != '^' && rng.chance((density * 1e6) as u64, 1_000_000) => '^',
+                _ => '.',
+            };
+            input.push(cell);
+            previous = cell;
+        }
+        input.push('\n');
+    }
+    input
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
 
-    println!("Part 1: {}", part1(&grid));
-    println!("Part 2: {}", part2(&grid));
+    type Input = Grid<char>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        utils::parse_grid(input, ".S^")
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_examples() {
+        examples::assert_examples::<Day07>(include_str!("README.md"));
+    }
 
     #[test]
     fn test_part1_simple() {
-        let grid = vec![
+        let grid = Grid::from_rows(vec![
             vec!['.', 'S', '.'],
             vec!['.', '.', '.'],
             vec!['.', '^', '.'],
             vec!['.', '.', '.'],
-        ];
+        ]);
         assert_eq!(part1(&grid), 1);
     }
 
     #[test]
     fn test_part1_no_branches() {
-        let grid = vec![
+        let grid = Grid::from_rows(vec![
             vec!['.', 'S', '.'],
             vec!['.', '.', '.'],
             vec!['.', '.', '.'],
-        ];
+        ]);
         assert_eq!(part1(&grid), 0);
     }
 
     #[test]
     fn test_part2_simple() {
   
-        let grid = vec![
+        let grid = Grid::from_rows(vec![
             vec!['.', 'S', '.'],
             vec!['.', '.', '.'],
             vec!['.', '^', '.'],
             vec!['.', '.', '.'],
-        ];
+        ]);
         assert_eq!(part2(&grid), 2);
     }
 
     #[test]
     fn test_part2_multiple_branches() {
-        let grid = vec![
-            vec!['.', '.', 'S', '.', '.'],
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '.', '^', '.', '.'],
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '^', '.', '^', '.'],
-            vec!['.', '.', '.', '.', '.'],
-        ];
+        let grid = utils::parse_grid("..S..\n.....\n..^..\n.....\n.^.^.\n.....\n", ".S^").unwrap();
+        assert_eq!(part1(&grid), 3);
         assert_eq!(part2(&grid), 4);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Day07::parse("..S..\n..x..\n"),
+            Err(ParseError::new(2, 3, "`.` or `S` or `^`", "`x`"))
+        );
+        assert_eq!(
+            Day07::parse("..S..\n...\n"),
+            Err(ParseError::new(2, 4, "a row of 5 cells", "3 cells"))
+        );
+    }
+
     #[test]
     fn test_empty_grid() {
-        let grid = vec![];
+        let grid = Grid::default();
         assert_eq!(part1(&grid), 0);
         assert_eq!(part2(&grid), 0);
     }
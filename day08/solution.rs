@@ -1,35 +1,42 @@
-use std::path::Path;
+use std::fmt::Write;
 
-#[path = "../utils.rs"]
-mod utils;
+use crate::geometry::Point3;
+use crate::rng::Rng;
+use crate::solution::{parse_token, ParseError, Solution};
+use crate::utils::Pattern;
 
-struct Point3D {
-    x: i64,
-    y: i64,
-    z: i64,
-}
-
-impl Point3D {
-    fn distance(&self, other: &Point3D) -> f64 {
-        let dx = (self.x - other.x) as f64;
-        let dy = (self.y - other.y) as f64;
-        let dz = (self.z - other.z) as f64;
-        (dx * dx + dy * dy + dz * dz).sqrt()
-    }
-}
+const COORDINATES: Pattern = Pattern::new("{an x coordinate},{a y coordinate},{a z coordinate}");
 
-fn parse_coordinates(lines: &[String]) -> Vec<Point3D> {
-    lines.iter()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let parts: Vec<i64> = line.split(',')
-                .map(|s| s.trim().parse().unwrap())
-                .collect();
-            Point3D { x: parts[0], y: parts[1], z: parts[2] }
+pub fn parse_coordinates<I>(lines: I) -> Result<Vec<Point3<i64>>, ParseError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    lines.into_iter()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().is_empty())
+        .map(|(idx, line)| {
+            let line = line.as_ref();
+            let texts: [&str; 3] = COORDINATES.fields(idx + 1, line)?;
+            let [x, y, z] = [(texts[0], "an x coordinate"), (texts[1], "a y coordinate"), (texts[2], "a z coordinate")]
+                .map(|(text, expected)| parse_coordinate(idx + 1, line, text, expected));
+            Ok(Point3::new(x?, y?, z?))
         })
         .collect()
 }
 
+/// Largest coordinate, either side of zero, so squared distances between boxes fit an i64
+pub const MAX_COORDINATE: i64 = 500_000_000;
+
+fn parse_coordinate(line_no: usize, line: &str, text: &str, expected: &str) -> Result<i64, ParseError> {
+    let value: i64 = parse_token(line_no, line, text, expected)?;
+    if value.abs() > MAX_COORDINATE {
+        let expected = format!("{} from -{} to {}", expected, MAX_COORDINATE, MAX_COORDINATE);
+        return Err(ParseError::at(line_no, line, text, expected));
+    }
+    Ok(value)
+}
+
 struct UnionFind {
     parent: Vec<usize>,
     size: Vec<usize>,
@@ -83,18 +90,17 @@ impl UnionFind {
     }
 }
 
-fn part1(lines: &[String], num_connections: usize) -> i64 {
-    let points = parse_coordinates(lines);
+pub fn part1(points: &[Point3<i64>], num_connections: usize) -> i64 {
     let n = points.len();
     
     let mut edges = Vec::new();
     for i in 0..n {
         for j in (i + 1)..n {
-            edges.push((i, j, points[i].distance(&points[j])));
+            edges.push((i, j, points[i].distance_squared(points[j])));
         }
     }
     
-    edges.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap());
+    edges.sort_by_key(|&(_, _, distance)| distance);
     
     let mut uf = UnionFind::new(n);
     let mut attempts = 0;
@@ -117,18 +123,17 @@ fn part1(lines: &[String], num_connections: usize) -> i64 {
     circuit_sizes.iter().take(3).map(|&s| s as i64).product()
 }
 
-fn part2(lines: &[String]) -> i64 {
-    let points = parse_coordinates(lines);
+pub fn part2(points: &[Point3<i64>]) -> i64 {
     let n = points.len();
     
     let mut edges = Vec::new();
     for i in 0..n {
         for j in (i + 1)..n {
-            edges.push((i, j, points[i].distance(&points[j])));
+            edges.push((i, j, points[i].distance_squared(points[j])));
         }
     }
     
-    edges.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap());
+    edges.sort_by_key(|&(_, _, distance)| distance);
     
     let mut uf = UnionFind::new(n);
     
@@ -140,76 +145,93 @@ fn part2(lines: &[String]) -> i64 {
         uf.union(*i, *j);
         
         if uf.count_circuits(n).len() == 1 {
-            return (points[*i].x * points[*j].x) as i64;
+            return points[*i].x * points[*j].x;
         }
     }
     
     0
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let lines = utils::read_lines(&input_path);
+/// Random junction boxes in a 100000-unit cube, 1000 per unit of `scale`
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..1000 * scale {
+        let [x, y, z] = [(); 3].map(|_| rng.range(0..=99_999));
+        writeln!(input, "{},{},{}", x, y, z).unwrap();
+    }
+    input
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input = Vec<Point3<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-    println!("Part 1: {}", part1(&lines, 1000));
-    println!("Part 2: {}", part2(&lines));
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_coordinates(input.lines())
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        part1(input, 1000)
+    }
+
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_examples() {
+        examples::assert_examples::<Day08>(include_str!("README.md"));
+    }
 
     #[test]
     fn test_part1_example() {
-        let input = vec![
-            "162,817,812".to_string(),
-            "57,618,57".to_string(),
-            "906,360,560".to_string(),
-            "592,479,940".to_string(),
-            "352,342,300".to_string(),
-            "466,668,158".to_string(),
-            "542,29,236".to_string(),
-            "431,825,988".to_string(),
-            "739,650,466".to_string(),
-            "52,470,668".to_string(),
-            "216,146,977".to_string(),
-            "819,987,18".to_string(),
-            "117,168,530".to_string(),
-            "805,96,715".to_string(),
-            "346,949,466".to_string(),
-            "970,615,88".to_string(),
-            "941,993,340".to_string(),
-            "862,61,35".to_string(),
-            "984,92,344".to_string(),
-            "425,690,689".to_string(),
-        ];
-        assert_eq!(part1(&input, 10), 40);
+        // The puzzle's example makes only the 10 shortest connections
+        let example = &examples::parse_examples(include_str!("README.md")).unwrap()[0];
+        assert_eq!(part1(&parse_coordinates(example.input.lines()).unwrap(), 10), 40);
     }
 
     #[test]
-    fn test_part2_example() {
-        let input = vec![
-            "162,817,812".to_string(),
-            "57,618,57".to_string(),
-            "906,360,560".to_string(),
-            "592,479,940".to_string(),
-            "352,342,300".to_string(),
-            "466,668,158".to_string(),
-            "542,29,236".to_string(),
-            "431,825,988".to_string(),
-            "739,650,466".to_string(),
-            "52,470,668".to_string(),
-            "216,146,977".to_string(),
-            "819,987,18".to_string(),
-            "117,168,530".to_string(),
-            "805,96,715".to_string(),
-            "346,949,466".to_string(),
-            "970,615,88".to_string(),
-            "941,993,340".to_string(),
-            "862,61,35".to_string(),
-            "984,92,344".to_string(),
-            "425,690,689".to_string(),
-        ];
-        assert_eq!(part2(&input), 25272);
+    fn test_parse_errors() {
+        let input = vec!["162,817,812".to_string(), "57,618".to_string()];
+        assert_eq!(
+            parse_coordinates(&input).err(),
+            Some(ParseError::new(2, 7, "`,`", "end of line"))
+        );
+
+        let input = vec!["162,8a7,812".to_string()];
+        assert_eq!(
+            parse_coordinates(&input).err(),
+            Some(ParseError::new(1, 5, "a y coordinate", "`8a7`"))
+        );
+
+        let input = vec!["1,2,3,4".to_string()];
+        assert_eq!(
+            parse_coordinates(&input).err(),
+            Some(ParseError::new(1, 5, "a z coordinate", "`3,4`"))
+        );
+
+        // Squaring the gap between these would overflow
+        let input = ["3000000000,0,0", "-3000000000,0,0"];
+        assert_eq!(
+            parse_coordinates(input).err(),
+            Some(ParseError::new(1, 1, "an x coordinate from -500000000 to 500000000", "`3000000000`"))
+        );
+        let input = ["500000000,0,0", "-500000000,0,-500000000"];
+        assert_eq!(part2(&parse_coordinates(input).unwrap()), -250_000_000_000_000_000);
     }
 }
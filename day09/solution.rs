@@ -1,25 +1,26 @@
-use std::path::Path;
+use std::fmt::Write;
 
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
+use crate::geometry::{Bounds2, Point2};
+use crate::rng::Rng;
+use crate::solution::{ParseError, Solution};
+use crate::utils::Pattern;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: i64,
-    y: i64,
-}
+type Point = Point2<i64>;
+
+const COORDINATES: Pattern = Pattern::new("{an x coordinate},{a y coordinate}");
 
-fn parse_points(lines: &[String]) -> Vec<Point> {
+pub fn parse_points<I>(lines: I) -> Result<Vec<Point>, ParseError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
     lines
-        .iter()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let parts: Vec<&str> = line.split(',').collect();
-            Point {
-                x: parts[0].parse().unwrap(),
-                y: parts[1].parse().unwrap(),
-            }
+        .into_iter()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().is_empty())
+        .map(|(idx, line)| {
+            let (x, y) = COORDINATES.parse(idx + 1, line.as_ref())?;
+            Ok(Point::new(x, y))
         })
         .collect()
 }
@@ -44,19 +45,10 @@ fn is_point_inside_polygon(point: Point, polygon: &[Point]) -> bool {
 
 // Check if a rectangle is completely inside the polygon
 fn is_rectangle_inside_polygon(p1: Point, p2: Point, polygon: &[Point]) -> bool {
-    let min_x = p1.x.min(p2.x);
-    let max_x = p1.x.max(p2.x);
-    let min_y = p1.y.min(p2.y);
-    let max_y = p1.y.max(p2.y);
-    
+    let bounds = Bounds2::spanning(p1, p2);
+    let corners = bounds.corners();
+
     // Check all four corners are inside or on the polygon boundary
-    let corners = [
-        Point { x: min_x, y: min_y },
-        Point { x: min_x, y: max_y },
-        Point { x: max_x, y: min_y },
-        Point { x: max_x, y: max_y },
-    ];
-    
     for corner in &corners {
         if !is_point_inside_polygon(*corner, polygon) && !polygon.contains(corner) {
             return false;
@@ -65,10 +57,10 @@ fn is_rectangle_inside_polygon(p1: Point, p2: Point, polygon: &[Point]) -> bool
     
     // Check that rectangle edges don't cross polygon edges
     let rect_edges = [
-        (Point { x: min_x, y: min_y }, Point { x: max_x, y: min_y }), // bottom
-        (Point { x: max_x, y: min_y }, Point { x: max_x, y: max_y }), // right
-        (Point { x: max_x, y: max_y }, Point { x: min_x, y: max_y }), // top
-        (Point { x: min_x, y: max_y }, Point { x: min_x, y: min_y }), // left
+        (corners[0], corners[1]), // bottom
+        (corners[1], corners[2]), // right
+        (corners[2], corners[3]), // top
+        (corners[3], corners[0]), // left
     ];
     
     for i in 0..polygon.len() {
@@ -104,14 +96,10 @@ fn edges_intersect(a1: Point, a2: Point, b1: Point, b2: Point) -> bool {
 }
 
 fn calculate_rectangle_area(p1: Point, p2: Point) -> i64 {
-    let width = (p1.x - p2.x).abs() + 1;
-    let height = (p1.y - p2.y).abs() + 1;
-    width * height
+    Bounds2::spanning(p1, p2).area()
 }
 
-fn part1(lines: &[String]) -> i64 {
-    let points = parse_points(lines);
-    
+pub fn part1(points: &[Point]) -> i64 {
     if points.len() < 2 {
         return 0;
     }
@@ -128,9 +116,7 @@ fn part1(lines: &[String]) -> i64 {
     max_area
 }
 
-fn part2(lines: &[String]) -> i64 {
-    let points = parse_points(lines);
-    
+pub fn part2(points: &[Point]) -> i64 {
     if points.len() < 2 {
         return 0;
     }
@@ -139,7 +125,7 @@ fn part2(lines: &[String]) -> i64 {
     
     for i in 0..points.len() {
         for j in i + 1..points.len() {
-            if is_rectangle_inside_polygon(points[i], points[j], &points) {
+            if is_rectangle_inside_polygon(points[i], points[j], points) {
                 let area = calculate_rectangle_area(points[i], points[j]);
                 max_area = max_area.max(area);
             }
@@ -149,17 +135,77 @@ fn part2(lines: &[String]) -> i64 {
     max_area
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let lines = utils::read_lines(&input_path);
+/// Random rectilinear polygon with about 500 red tiles per unit of `scale`, listed in
+/// order around the loop. It is a run of columns whose spans all cross the middle row, so
+/// the outline never touches itself.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    const HEIGHT: i64 = 100_000;
+    let columns = 124 * scale;
+    let mut xs = vec![rng.range(0..=1000)];
+    for _ in 0..columns {
+        let last = xs[xs.len() - 1];
+        xs.push(last + rng.range(1..=800));
+    }
+    let mut spans: Vec<(i64, i64)> = Vec::with_capacity(columns);
+    for _ in 0..columns {
+        let (mut bottom, mut top) = (rng.range(0..=HEIGHT / 2 - 1), rng.range(HEIGHT / 2 + 1..=HEIGHT));
+        if let Some(&(last_bottom, last_top)) = spans.last() {
+            // Equal neighbours would merge two corners into the middle of an edge
+            while bottom == last_bottom {
+                bottom = rng.range(0..=HEIGHT / 2 - 1);
+            }
+            while top == last_top {
+                top = rng.range(HEIGHT / 2 + 1..=HEIGHT);
+            }
+        }
+        spans.push((bottom, top));
+    }
 
-    println!("Part 1: {}", part1(&lines));
-    println!("Part 2: {}", part2(&lines));
+    let mut input = String::new();
+    for (idx, &(_, top)) in spans.iter().enumerate() {
+        writeln!(input, "{},{}\n{},{}", xs[idx], top, xs[idx + 1], top).unwrap();
+    }
+    for (idx, &(bottom, _)) in spans.iter().enumerate().rev() {
+        writeln!(input, "{},{}\n{},{}", xs[idx + 1], bottom, xs[idx], bottom).unwrap();
+    }
+    input
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Point>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_points(input.lines())
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_examples() {
+        examples::assert_examples::<Day09>(include_str!("README.md"));
+    }
 
     #[test]
     fn test_parse_points() {
@@ -168,103 +214,88 @@ mod tests {
             "11,1".to_string(),
             "11,7".to_string(),
         ];
-        let points = parse_points(&input);
+        let points = parse_points(&input).unwrap();
         assert_eq!(points.len(), 3);
-        assert_eq!(points[0], Point { x: 7, y: 1 });
-        assert_eq!(points[1], Point { x: 11, y: 1 });
-        assert_eq!(points[2], Point { x: 11, y: 7 });
+        assert_eq!(points[0], Point::new(7, 1));
+        assert_eq!(points[1], Point::new(11, 1));
+        assert_eq!(points[2], Point::new(11, 7));
     }
 
     #[test]
     fn test_calculate_rectangle_area() {
-        let p1 = Point { x: 1, y: 1 };
-        let p2 = Point { x: 5, y: 3 };
+        let p1 = Point::new(1, 1);
+        let p2 = Point::new(5, 3);
         assert_eq!(calculate_rectangle_area(p1, p2), 15); // (5-1+1) * (3-1+1) = 5 * 3
         
-        let p3 = Point { x: 9, y: 5 };
-        let p4 = Point { x: 2, y: 3 };
+        let p3 = Point::new(9, 5);
+        let p4 = Point::new(2, 3);
         assert_eq!(calculate_rectangle_area(p3, p4), 24); // (9-2+1) * (5-3+1) = 8 * 3
     }
 
     #[test]
     fn test_point_inside_polygon() {
         let polygon = vec![
-            Point { x: 0, y: 0 },
-            Point { x: 10, y: 0 },
-            Point { x: 10, y: 10 },
-            Point { x: 0, y: 10 },
+            Point::new(0, 0),
+            Point::new(10, 0),
+            Point::new(10, 10),
+            Point::new(0, 10),
         ];
         
         // Point clearly inside
-        assert!(is_point_inside_polygon(Point { x: 5, y: 5 }, &polygon));
+        assert!(is_point_inside_polygon(Point::new(5, 5), &polygon));
         
         // Point clearly outside
-        assert!(!is_point_inside_polygon(Point { x: 15, y: 15 }, &polygon));
-        assert!(!is_point_inside_polygon(Point { x: -5, y: 5 }, &polygon));
+        assert!(!is_point_inside_polygon(Point::new(15, 15), &polygon));
+        assert!(!is_point_inside_polygon(Point::new(-5, 5), &polygon));
     }
 
     #[test]
     fn test_edges_intersect() {
-        let a1 = Point { x: 0, y: 0 };
-        let a2 = Point { x: 10, y: 10 };
-        let b1 = Point { x: 0, y: 10 };
-        let b2 = Point { x: 10, y: 0 };
+        let a1 = Point::new(0, 0);
+        let a2 = Point::new(10, 10);
+        let b1 = Point::new(0, 10);
+        let b2 = Point::new(10, 0);
         assert!(edges_intersect(a1, a2, b1, b2));
         
-        let c1 = Point { x: 0, y: 0 };
-        let c2 = Point { x: 5, y: 0 };
-        let d1 = Point { x: 0, y: 5 };
-        let d2 = Point { x: 5, y: 5 };
+        let c1 = Point::new(0, 0);
+        let c2 = Point::new(5, 0);
+        let d1 = Point::new(0, 5);
+        let d2 = Point::new(5, 5);
         assert!(!edges_intersect(c1, c2, d1, d2));
         
-        let e1 = Point { x: 0, y: 0 };
-        let e2 = Point { x: 5, y: 5 };
-        let f1 = Point { x: 5, y: 5 };
-        let f2 = Point { x: 10, y: 0 };
+        let e1 = Point::new(0, 0);
+        let e2 = Point::new(5, 5);
+        let f1 = Point::new(5, 5);
+        let f2 = Point::new(10, 0);
         assert!(!edges_intersect(e1, e2, f1, f2));
     }
 
     #[test]
-    fn test_part1_example() {
-        let input = vec![
-            "7,1".to_string(),
-            "11,1".to_string(),
-            "11,7".to_string(),
-            "9,7".to_string(),
-            "9,5".to_string(),
-            "2,5".to_string(),
-            "2,3".to_string(),
-            "7,3".to_string(),
-        ];
-        assert_eq!(part1(&input), 50);
+    fn test_empty_input() {
+        let input: Vec<String> = vec![];
+        assert_eq!(part1(&parse_points(&input).unwrap()), 0);
+        assert_eq!(part2(&parse_points(&input).unwrap()), 0);
     }
 
     #[test]
-    fn test_part2_example() {
-        let input = vec![
-            "7,1".to_string(),
-            "11,1".to_string(),
-            "11,7".to_string(),
-            "9,7".to_string(),
-            "9,5".to_string(),
-            "2,5".to_string(),
-            "2,3".to_string(),
-            "7,3".to_string(),
-        ];
-        assert_eq!(part2(&input), 24);
+    fn test_single_point() {
+        let input = vec!["5,5".to_string()];
+        assert_eq!(part1(&parse_points(&input).unwrap()), 0);
+        assert_eq!(part2(&parse_points(&input).unwrap()), 0);
     }
 
     #[test]
-    fn test_empty_input() {
-        let input = vec![];
-        assert_eq!(part1(&input), 0);
-        assert_eq!(part2(&input), 0);
-    }
+    fn test_parse_errors() {
+        let input = vec!["7,1".to_string(), "11".to_string()];
+        assert_eq!(
+            parse_points(&input).err(),
+            Some(ParseError::new(2, 3, "`,`", "end of line"))
+        );
 
-    #[test]
-    fn test_single_point() {
-        let input = vec!["5,5".to_string()];
-        assert_eq!(part1(&input), 0);
-        assert_eq!(part2(&input), 0);
+        let input = vec!["7;1".to_string()];
+        assert_eq!(
+            parse_points(&input).err(),
+            Some(ParseError::new(1, 1, "an x coordinate", "`7;1`"))
+        );
     }
 }
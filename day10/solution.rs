@@ -1,51 +1,84 @@
-use std::path::Path;
-use regex::Regex;
+use std::fmt::Write;
 
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
+use crate::rng::Rng;
+use crate::solution::{parse_token, ParseError, Solution};
+use crate::utils::Pattern;
 
+const MACHINE: Pattern = Pattern::new("[{a light diagram}] {button wiring schematics} {{{joltage requirements}}}");
 
-type ParsedLine = (u32, Vec<u32>, Vec<i64>, usize); 
+/// Most buttons on one machine; part 1 tries all 2^24 (about 16 million) subsets of them
+pub const MAX_BUTTONS: usize = 24;
 
+pub type ParsedLine = (u32, Vec<u32>, Vec<i64>, usize);
 
-fn parse_line(line: &str) -> ParsedLine {
-    let diagrams_re = Regex::new(r"\[([.#]+)\]").unwrap();
-    let schematics_re = Regex::new(r"\(([0-9,]+)\)").unwrap();
-    let requirements_re = Regex::new(r"\{([\d,]+)\}").unwrap();
-    
-    let (target, n_lights) = diagrams_re.captures(line)
-        .map(|c| {
-            let pattern = &c[1];
-            let n = pattern.len();
-            let target = pattern.chars()
-                .enumerate()
-                .fold(0u32, |acc, (i, ch)| {
-                    if ch == '#' {
-                        acc | (1u32 << i)
-                    } else {
-                        acc
-                    }
-                });
-            (target, n)
-        })
-        .unwrap_or((0, 0));
+fn parse_line(line_no: usize, line: &str) -> Result<ParsedLine, ParseError> {
+    let [pattern, schematics, requirements_str] = MACHINE.fields(line_no, line)?;
+    if let Some((col, c)) = pattern.char_indices().find(|&(_, c)| c != '.' && c != '#') {
+        return Err(ParseError::at(line_no, line, &pattern[col..col + c.len_utf8()], "`.` or `#`"));
+    }
+    // Part 1 masks the lights with `(1 << lights) - 1`, which needs a spare bit
+    if pattern.len() > 31 {
+        return Err(ParseError::at(line_no, line, pattern, "at most 31 lights"));
+    }
+    let n_lights = pattern.len();
+    let target = pattern.chars()
+        .enumerate()
+        .fold(0u32, |acc, (i, ch)| {
+            if ch == '#' {
+                acc | (1u32 << i)
+            } else {
+                acc
+            }
+        });
     
-    let buttons: Vec<u32> = schematics_re.captures_iter(line)
-        .map(|c| {
-            c[1].split(',')
-                .filter_map(|s| s.parse::<usize>().ok())
-                .fold(0u32, |acc, bit_pos| acc | (1u32 << bit_pos))
-        })
-        .collect();
+    let mut buttons: Vec<u32> = Vec::new();
+    for schematic in schematics.split_whitespace() {
+        // Part 1 tries every subset of the buttons, so each one doubles its work
+        if buttons.len() == MAX_BUTTONS {
+            return Err(ParseError::at(line_no, line, schematic, format!("at most {} buttons", MAX_BUTTONS)));
+        }
+        let wiring = schematic
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(|| ParseError::at(line_no, line, schematic, "a button `(...)`"))?;
+        let mut button = 0u32;
+        for s in wiring.split(',') {
+            let bit_pos: u32 = parse_token(line_no, line, s, "a light index")?;
+            if bit_pos as usize >= n_lights {
+                return Err(ParseError::at(line_no, line, s, format!("a light index below {}", n_lights)));
+            }
+            button |= 1u32 << bit_pos;
+        }
+        buttons.push(button);
+    }
     
-    let requirements: Vec<i64> = requirements_re.captures(line)
-        .map(|c| c[1].split(',')
-            .filter_map(|s| s.parse().ok())
-            .collect())
-        .unwrap_or_default();
+    let tokens: Vec<&str> = requirements_str.split(',').collect();
+    // Each counter is one bit of a button's u32 wiring
+    if let Some(extra) = tokens.get(32) {
+        return Err(ParseError::at(line_no, line, extra, "at most 32 joltage requirements"));
+    }
+    let requirements: Vec<i64> = tokens
+        .iter()
+        .map(|s| parse_token(line_no, line, s, "a joltage requirement").map(|value: u32| i64::from(value)))
+        .collect::<Result<_, _>>()?;
+    if requirements.len() != n_lights {
+        let expected = format!("{} joltage requirements, one per light", n_lights);
+        return Err(ParseError::at(line_no, line, requirements_str, expected));
+    }
     
-    (target, buttons, requirements, n_lights)
+    Ok((target, buttons, requirements, n_lights))
+}
+
+pub fn parse_machines<I>(lines: I) -> Result<Vec<ParsedLine>, ParseError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    lines.into_iter()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().trim().is_empty())
+        .map(|(idx, line)| parse_line(idx + 1, line.as_ref()))
+        .collect()
 }
 
 fn minimal_presses(buttons: &[u32], target: u32, n_lights: usize) -> usize {
@@ -183,11 +216,7 @@ fn minimal_presses_part2(buttons: &[u32], requirements: &[i64], n_counters: usiz
     best_total
 }
 
-fn part1(_lines: &[String]) -> i64 {
-    let all = _lines.iter()
-        .map(|line| parse_line(line))
-        .collect::<Vec<_>>();  
-
+pub fn part1(all: &[ParsedLine]) -> i64 {
     let mut total = 0;
     
     for (_target, buttons, _requirements, n_lights) in all.iter() {
@@ -197,55 +226,116 @@ fn part1(_lines: &[String]) -> i64 {
     total
 }
 
-fn part2(_lines: &[String]) -> i64 {
-    _lines.iter()
-        .map(|line| parse_line(line))
+pub fn part2(all: &[ParsedLine]) -> i64 {
+    all.iter()
         .map(|(_target, buttons, requirements, _n_lights)| {
-            minimal_presses_part2(&buttons, &requirements, requirements.len())
+            minimal_presses_part2(buttons, requirements, requirements.len())
         })
         .filter(|&result| result < i64::MAX)
         .sum()
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let lines = utils::read_lines(&input_path);
+/// Random machines, 180 per unit of `scale`. Each diagram is the toggle of some buttons
+/// and each joltage requirement comes from real press counts, so every machine is solvable.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..180 * scale {
+        let lights = rng.range(4..=10) as usize;
+        let button_count = rng.range(lights as i64 - 2..=lights as i64 + 2) as usize;
+        let buttons: Vec<Vec<usize>> = (0..button_count)
+            .map(|_| {
+                let wiring: Vec<usize> = (0..lights).filter(|_| rng.chance(2, 5)).collect();
+                if wiring.is_empty() { vec![rng.index(lights)] } else { wiring }
+            })
+            .collect();
+
+        let mut diagram = vec!['.'; lights];
+        let mut joltage = vec![0; lights];
+        for wiring in &buttons {
+            let toggled = rng.chance(1, 2);
+            let presses = rng.range(0..=30);
+            for &light in wiring {
+                if toggled {
+                    diagram[light] = if diagram[light] == '#' { '.' } else { '#' };
+                }
+                joltage[light] += presses;
+            }
+        }
 
-    println!("Part 1: {}", part1(&lines));
-    println!("Part 2: {}", part2(&lines));
+        let diagram: String = diagram.into_iter().collect();
+        let schematics: Vec<String> = buttons
+            .iter()
+            .map(|wiring| format!("({})", wiring.iter().map(usize::to_string).collect::<Vec<_>>().join(",")))
+            .collect();
+        let joltage: Vec<String> = joltage.iter().map(i64::to_string).collect();
+        writeln!(input, "[{}] {} {{{}}}", diagram, schematics.join(" "), joltage.join(",")).unwrap();
+    }
+    input
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<ParsedLine>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_machines(input.lines())
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_examples() {
+        examples::assert_examples::<Day10>(include_str!("README.md"));
+    }
 
     #[test]
     fn test_part1_simple() {
         // Single button, single light
         let input = vec!["[#] (0) {1}".to_string()];
-        assert_eq!(part1(&input), 1);
+        assert_eq!(part1(&parse_machines(&input).unwrap()), 1);
         
         // Target already at 0, no presses needed
-        let input = vec!["[..] (0) (1) {0}".to_string()];
-        assert_eq!(part1(&input), 0);
+        let input = vec!["[..] (0) (1) {0,0}".to_string()];
+        assert_eq!(part1(&parse_machines(&input).unwrap()), 0);
     }
     
     #[test]
     fn test_part1_multiple_buttons() {
         // Two buttons affecting same light
         let input = vec!["[#] (0) (0) {1}".to_string()];
-        assert_eq!(part1(&input), 1); // Either button works
+        assert_eq!(part1(&parse_machines(&input).unwrap()), 1); // Either button works
         
         // Buttons that cancel out
-        let input = vec!["[..] (0,1) (0,1) {0}".to_string()];
-        assert_eq!(part1(&input), 0); // Already at target
+        let input = vec!["[..] (0,1) (0,1) {0,0}".to_string()];
+        assert_eq!(part1(&parse_machines(&input).unwrap()), 0); // Already at target
     }
     
     #[test]
     fn test_part2_simple() {
         // Single button, single counter - need 5 presses
         let input = vec!["[.] (0) {5}".to_string()];
-        assert_eq!(part2(&input), 5);
+        assert_eq!(part2(&parse_machines(&input).unwrap()), 5);
     }
     
     #[test]
@@ -253,7 +343,7 @@ mod tests {
         // Two buttons, two counters - unique solution
         // b0 affects counter 0, b1 affects counter 1
         let input = vec!["[..] (0) (1) {3,4}".to_string()];
-        assert_eq!(part2(&input), 7); // 3 + 4 = 7 total presses
+        assert_eq!(part2(&parse_machines(&input).unwrap()), 7); // 3 + 4 = 7 total presses
     }
     
     #[test]
@@ -261,17 +351,7 @@ mod tests {
         // More equations than variables - should still find solution if consistent
         let input = vec!["[..] (0,1) (0,1) {5,5}".to_string()];
         // Both equations say b0 + b1 = 5, so any split works, minimum is 5
-        assert_eq!(part2(&input), 5);
-    }
-
-    #[test]
-    fn test_part2_examples() {
-        let input = vec![
-            "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}".to_string(),
-            "[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}".to_string(),
-            "[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}".to_string(),
-        ];
-        assert_eq!(part2(&input), 33);
+        assert_eq!(part2(&parse_machines(&input).unwrap()), 5);
     }
     
     #[test]
@@ -279,7 +359,78 @@ mod tests {
         let input = vec![
             "[##........] (4,5,6,7,9) (0,1,2,3,4,5,6,8,9) (0,2,3,6,7,9) (6,8) (0,4,5,8) (0,4,5,7) (1,8,9) (1,2,3,6,9) {42,226,215,215,36,36,222,32,37,238}".to_string(),
         ];
-        let result = part2(&input);
+        let result = part2(&parse_machines(&input).unwrap());
         assert_eq!(result, 262, "Machine 12 should return 262");
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_line(1, "(0) {1}"),
+            Err(ParseError::new(1, 1, "`[`", "`(`"))
+        );
+        assert_eq!(
+            parse_line(2, "[#] (0,,1) {1}"),
+            Err(ParseError::new(2, 8, "a light index", "`,`"))
+        );
+        assert_eq!(
+            parse_line(3, "[#] (0) (40) {1}"),
+            Err(ParseError::new(3, 10, "a light index below 1", "`40`"))
+        );
+        assert_eq!(
+            parse_line(3, "[#.] (0) (2) {1,1}"),
+            Err(ParseError::new(3, 11, "a light index below 2", "`2`"))
+        );
+        assert_eq!(
+            parse_line(7, "[#.] (0) (1) {1,-2}"),
+            Err(ParseError::new(7, 17, "a joltage requirement", "`-2`"))
+        );
+        assert_eq!(
+            parse_line(4, "[#] (0)"),
+            Err(ParseError::new(4, 8, "`{`", "end of line"))
+        );
+        assert_eq!(
+            parse_line(5, "[#x] (0) {1}"),
+            Err(ParseError::new(5, 3, "`.` or `#`", "`x`"))
+        );
+        assert_eq!(
+            parse_line(6, "[#] 0 {1}"),
+            Err(ParseError::new(6, 5, "a button `(...)`", "`0`"))
+        );
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let lights = |count: usize| "#".repeat(count);
+        let requirements = |count: usize| vec!["1"; count].join(",");
+        let line = |lights: &str, buttons: usize, requirements: &str| {
+            format!("[{}] {}{{{}}}", lights, "(0) ".repeat(buttons), requirements)
+        };
+
+        assert!(parse_line(1, &line(&lights(31), MAX_BUTTONS, &requirements(31))).is_ok());
+        assert_eq!(
+            parse_line(1, &line(&lights(32), 1, &requirements(32))),
+            Err(ParseError::new(1, 2, "at most 31 lights", format!("`{}`", lights(32))))
+        );
+        assert_eq!(
+            parse_line(2, &line("#", MAX_BUTTONS + 1, "1")),
+            Err(ParseError::new(2, 4 + MAX_BUTTONS * 4 + 1, "at most 24 buttons", "`(0)`"))
+        );
+        assert_eq!(
+            parse_line(3, &line("#", 1, &requirements(33))),
+            Err(ParseError::new(3, 10 + 32 * 2, "at most 32 joltage requirements", "`1`"))
+        );
+        assert_eq!(
+            parse_line(4, "[##] (0) {1}"),
+            Err(ParseError::new(4, 11, "2 joltage requirements, one per light", "`1`"))
+        );
+    }
+
+    #[test]
+    fn test_blank_lines_are_skipped() {
+        let machines = parse_machines(["[.#] (1) {0,2}", "", "[#.] (0) {3,0}", "  "]).unwrap();
+        assert_eq!(machines.len(), 2);
+        // Line numbers still count the blank lines
+        assert_eq!(parse_machines(["[#] (0) {1}", "", "[#] 0 {1}"]).unwrap_err().line, 3);
+    }
 }
@@ -1,27 +1,41 @@
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
 
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
+use crate::rng::Rng;
+use crate::solution::{ParseError, Solution};
+use crate::utils::Pattern;
 
-fn parse_to_hashmap(input: &[String]) -> HashMap<String, Vec<String>> {
+const DEVICE: Pattern = Pattern::new("{a device name}:{its outputs}");
+
+pub type Graph = HashMap<String, Vec<String>>;
+
+pub fn parse_to_hashmap<I>(lines: I) -> Result<Graph, ParseError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
     let mut map = HashMap::new();
     
-    for line in input {
-        if let Some((key, values)) = line.split_once(':') {
-            let key = key.trim().to_string();
-            
-            let values: Vec<String> = values
-                .split_whitespace()
-                .map(|s| s.to_string())
-                .collect();
-            
-            map.insert(key, values);
+    for (idx, line) in lines.into_iter().enumerate() {
+        let line = line.as_ref();
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let [key, values] = DEVICE.fields(idx + 1, line)?;
+        if key.is_empty() {
+            return Err(ParseError::at(idx + 1, line, key, "a device name"));
         }
+        let key = key.to_string();
+        
+        let values: Vec<String> = values
+            .split_whitespace()
+            .map(|s| s.to_string())
+            .collect();
+        
+        map.insert(key, values);
     }
     
-    map
+    Ok(map)
 }
 
 fn count_paths_to_out(
@@ -61,71 +75,141 @@ fn count_paths_to_out(
     total
 }
 
-fn part1(lines: &[String]) -> i64 {
-    
-    let graph = parse_to_hashmap(lines);
+pub fn part1(graph: &Graph) -> i64 {
     let required = vec![];
     let mut memo = HashMap::new();
-    count_paths_to_out("you", &graph, &required, 0, 0, &mut memo)
+    count_paths_to_out("you", graph, &required, 0, 0, &mut memo)
 }
 
-fn part2(lines: &[String]) -> i64 {
-    let graph = parse_to_hashmap(lines);
+pub fn part2(graph: &Graph) -> i64 {
     let required = vec!["fft".to_string(), "dac".to_string()];
     let target_mask = (1 << required.len()) - 1; 
     let mut memo = HashMap::new();
-    count_paths_to_out("svr", &graph, &required, 0, target_mask, &mut memo)
+    count_paths_to_out("svr", graph, &required, 0, target_mask, &mut memo)
+}
+
+/// Random device network of about 600 devices per unit of `scale`, listed in random
+/// order. `svr` reaches `out` through 24 stages of one to three short parallel chains,
+/// with `you`, `fft` and `dac` where stages meet and an extra chain around each of `fft`
+/// and `dac`. The remaining devices are dead-end spurs wired straight to `out`, so larger
+/// networks get wider rather than deeper and path counts stay within `i64`.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    const STAGES: usize = 24;
+    const RESERVED: [&str; 5] = ["svr", "you", "fft", "dac", "out"];
+    let devices = 600 * scale;
+
+    let mut length = 3;
+    while 26_usize.pow(length as u32) < 4 * devices {
+        length += 1;
+    }
+    let mut taken: HashSet<String> = RESERVED.iter().map(|name| name.to_string()).collect();
+    let mut fresh_name = |rng: &mut Rng| loop {
+        let name: String = (0..length).map(|_| char::from(b'a' + rng.below(26) as u8)).collect();
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    let (first, second) = if rng.chance(1, 2) { ("fft", "dac") } else { ("dac", "fft") };
+    let joins: Vec<String> = (0..=STAGES)
+        .map(|stage| match stage {
+            0 => "svr".to_string(),
+            3 => "you".to_string(),
+            10 => first.to_string(),
+            17 => second.to_string(),
+            STAGES => "out".to_string(),
+            _ => fresh_name(rng),
+        })
+        .collect();
+
+    let mut outputs: HashMap<String, Vec<String>> = HashMap::new();
+    let mut chain_devices = Vec::new();
+    let mut add_chain = |rng: &mut Rng, from: &str, to: &str, length: i64| {
+        let mut previous = from.to_string();
+        for _ in 0..length {
+            let device = fresh_name(rng);
+            outputs.entry(previous).or_default().push(device.clone());
+            chain_devices.push(device.clone());
+            previous = device;
+        }
+        outputs.entry(previous).or_default().push(to.to_string());
+    };
+    for (idx, stage) in joins.windows(2).enumerate() {
+        for chain in 0..rng.range(1..=3) {
+            // At most one direct link per stage, so no device lists an output twice
+            let length = rng.range(i64::from(chain > 0)..=6);
+            add_chain(rng, &stage[0], &stage[1], length);
+        }
+        if stage[1] == "fft" || stage[1] == "dac" {
+            let length = rng.range(1..=6);
+            add_chain(rng, &stage[0], &joins[idx + 2], length);
+        }
+    }
+
+    for _ in outputs.len()..devices {
+        let spur = fresh_name(rng);
+        let parent = rng.choose(&chain_devices).clone();
+        outputs.entry(parent).or_default().push(spur.clone());
+        outputs.insert(spur, vec!["out".to_string()]);
+    }
+
+    let mut lines: Vec<String> = outputs
+        .into_iter()
+        .map(|(device, outputs)| format!("{}: {}\n", device, outputs.join(" ")))
+        .collect();
+    lines.sort_unstable();
+    rng.shuffle(&mut lines);
+    lines.concat()
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let lines = utils::read_lines(&input_path);
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
 
-    println!("Part 1: {}", part1(&lines));
-    println!("Part 2: {}", part2(&lines));
+    type Input = Graph;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_to_hashmap(input.lines())
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(part2(input))
+    }
+
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples;
 
     #[test]
-    fn test_part1() {
-        let input = vec![
-            "you: aaa bbb".to_string(),
-            "aaa: fft".to_string(),
-            "fft: ccc".to_string(),
-            "bbb: tty".to_string(),
-            "tty: ccc".to_string(),
-            "ccc: ddd eee".to_string(),
-            "ddd: hub".to_string(),
-            "hub: fff".to_string(),
-            "eee: dac".to_string(),
-            "dac: fff".to_string(),
-            "fff: ggg hhh".to_string(),
-            "ggg: out".to_string(),
-            "hhh: out".to_string(),
-        ];
-        assert_eq!(part1(&input), 8);
+    fn test_examples() {
+        examples::assert_examples::<Day11>(include_str!("README.md"));
     }
 
     #[test]
-    fn test_part2() {
-        let input = vec![
-            "svr: aaa bbb".to_string(),
-            "aaa: fft".to_string(),
-            "fft: ccc".to_string(),
-            "bbb: tty".to_string(),
-            "tty: ccc".to_string(),
-            "ccc: ddd eee".to_string(),
-            "ddd: hub".to_string(),
-            "hub: fff".to_string(),
-            "eee: dac".to_string(),
-            "dac: fff".to_string(),
-            "fff: ggg hhh".to_string(),
-            "ggg: out".to_string(),
-            "hhh: out".to_string(),
-        ];
-        assert_eq!(part2(&input), 2);
+    fn test_parse_errors() {
+        let input = vec!["you: aaa".to_string(), "aaa out".to_string()];
+        assert_eq!(
+            parse_to_hashmap(&input),
+            Err(ParseError::new(2, 8, "`:`", "end of line"))
+        );
+
+        let input = vec![": aaa".to_string()];
+        assert_eq!(
+            parse_to_hashmap(&input),
+            Err(ParseError::new(1, 1, "a device name", "`:`"))
+        );
     }
 }
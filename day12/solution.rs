@@ -1,53 +1,104 @@
-use std::path::Path;
 use std::collections::HashMap;
+use std::fmt::Write;
 
-// Add the parent directory to access utils
-#[path = "../utils.rs"]
-mod utils;
+use crate::rng::Rng;
+use crate::solution::{parse_token, ParseError, Solution};
+use crate::utils::{self, Pattern};
+
+const REGION: Pattern = Pattern::new("{region dimensions}:{shape counts}").named("a shape `N:` or a region `WxH: counts`");
+const DIMENSIONS: Pattern = Pattern::new("{a region width}x{a region height}").named("region dimensions `WxH`");
 
 #[derive(Debug)]
-struct GridSpec {
+pub struct GridSpec {
     width: usize,
     height: usize,
     pattern_counts: Vec<(usize, usize)>
 }
 
-fn parse_input(lines: &[String]) -> (HashMap<usize, usize>, Vec<GridSpec>) {
+pub type Presents = (HashMap<usize, usize>, Vec<GridSpec>);
+
+/// Shape whose rows are still being read
+struct ShapeRows {
+    id: usize,
+    rows: usize,
+    cells: usize,
+    /// Line and end-of-line column of the last row read, where a missing row is reported
+    last_line: usize,
+    last_end: usize,
+}
+
+impl ShapeRows {
+    fn unfinished(&self) -> ParseError {
+        ParseError::new(self.last_line, self.last_end, "three rows of shape cells", "end of line")
+    }
+}
+
+pub fn parse_input<I>(lines: I) -> Result<Presents, ParseError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
     let mut pattern_cells = HashMap::new();
     let mut grid_specs = Vec::new();
-    let mut i = 0;
-    
-    while i < lines.len() {
-        let line = lines[i].trim();
-        
-        if let Some(pattern_id) = line.strip_suffix(':').and_then(|s| s.parse().ok()) {
-            let cell_count: usize = lines[i+1..].iter().take(3)
-                .flat_map(|line| line.chars())
-                .filter(|&c| c == '#')
-                .count();
-            pattern_cells.insert(pattern_id, cell_count);
-            i += 4;
-            continue;
+    let mut current_section = None;
+    let mut shape: Option<ShapeRows> = None;
+
+    for (section, line_no, line) in utils::section_lines(lines) {
+        let line = line.as_ref();
+        let end = line.chars().count() + 1;
+
+        // A section opening with `N:` holds that shape's three rows, then maybe regions
+        if current_section != Some(section) {
+            current_section = Some(section);
+            if let Some(shape) = &shape {
+                return Err(shape.unfinished());
+            }
+            if let Some(id) = line.trim().strip_suffix(':').and_then(|s| s.parse().ok()) {
+                shape = Some(ShapeRows { id, rows: 0, cells: 0, last_line: line_no, last_end: end });
+                continue;
+            }
         }
-        
-        if let Some((dims, counts_str)) = line.split_once(':') {
-            if let Some((w, h)) = dims.split_once('x') {
-                if let (Ok(width), Ok(height)) = (w.parse(), h.parse()) {
-                    let pattern_counts: Vec<_> = counts_str
-                        .split_whitespace()
-                        .enumerate()
-                        .filter_map(|(id, s)| s.parse().ok().filter(|&count| count > 0).map(|count| (id, count)))
-                        .collect();
-                    
-                    grid_specs.push(GridSpec { width, height, pattern_counts });
-                }
+
+        if let Some(partial) = shape.as_mut() {
+            if let Some((col, c)) = line.char_indices().find(|&(_, c)| c != '#' && c != '.') {
+                return Err(ParseError::at(line_no, line, &line[col..col + c.len_utf8()], "`#` or `.`"));
+            }
+            partial.rows += 1;
+            partial.cells += line.chars().filter(|&c| c == '#').count();
+            partial.last_line = line_no;
+            partial.last_end = end;
+            if partial.rows == 3 {
+                pattern_cells.insert(partial.id, partial.cells);
+                shape = None;
             }
+            continue;
         }
-        
-        i += 1;
+
+        grid_specs.push(parse_region(line_no, line, &pattern_cells)?);
     }
-    
-    (pattern_cells, grid_specs)
+    if let Some(shape) = &shape {
+        return Err(shape.unfinished());
+    }
+
+    Ok((pattern_cells, grid_specs))
+}
+
+fn parse_region(line_no: usize, line: &str, pattern_cells: &HashMap<usize, usize>) -> Result<GridSpec, ParseError> {
+    let [dims, counts_str] = REGION.fields(line_no, line)?;
+    let (width, height) = DIMENSIONS.parse_in(line_no, line, dims)?;
+
+    let mut pattern_counts = Vec::new();
+    for (id, s) in counts_str.split_whitespace().enumerate() {
+        let count: usize = parse_token(line_no, line, s, "a shape count")?;
+        if count > 0 && !pattern_cells.contains_key(&id) {
+            return Err(ParseError::at(line_no, line, s, format!("no presents of undefined shape {}", id)));
+        }
+        if count > 0 {
+            pattern_counts.push((id, count));
+        }
+    }
+
+    Ok(GridSpec { width, height, pattern_counts })
 }
 
 fn solve_grid(
@@ -71,74 +122,122 @@ fn solve_grid(
     if usage < 0.85 { 1 } else { 0 }
 }
 
-fn part1(_lines: &[String]) -> i64 {
-    let (pattern_cells, grid_specs) = parse_input(_lines);
-    
+pub fn part1(presents: &Presents) -> i64 {
+    let (pattern_cells, grid_specs) = presents;
+
     grid_specs
         .iter()
-        .map(|spec| solve_grid(spec.width, spec.height, &pattern_cells, &spec.pattern_counts))
+        .map(|spec| solve_grid(spec.width, spec.height, pattern_cells, &spec.pattern_counts))
         .sum()
 }
 
-pub fn main() {
-    let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-    let lines = utils::read_lines(&input_path);
+/// Random present shapes and 1000 regions per unit of `scale`. Each region is either
+/// comfortably large enough for its presents or clearly too small.
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    const SHAPES: usize = 6;
+    let mut input = String::new();
+    let mut shape_cells = Vec::with_capacity(SHAPES);
+    for shape in 0..SHAPES {
+        let mut cells = [false; 9];
+        cells[4] = true;
+        let filled = rng.range(5..=7) as usize;
+        while cells.iter().filter(|&&cell| cell).count() < filled {
+            cells[rng.index(9)] = true;
+        }
+        shape_cells.push(filled);
+        writeln!(input, "{}:", shape).unwrap();
+        for row in cells.chunks(3) {
+            input.extend(row.iter().map(|&cell| if cell { '#' } else { '.' }));
+            input.push('\n');
+        }
+        input.push('\n');
+    }
 
-    println!("Part 1: {}", part1(&lines));
+    for _ in 0..1000 * scale {
+        let (width, height) = (rng.range(35..=50) as usize, rng.range(35..=50) as usize);
+        let area = width * height;
+        let target = if rng.chance(1, 2) { area * 7 / 10 } else { area + area / 20 };
+        let mut counts = [0; SHAPES];
+        let mut cells = 0;
+        while cells < target {
+            let shape = rng.index(SHAPES);
+            counts[shape] += 1;
+            cells += shape_cells[shape];
+        }
+        let counts: Vec<String> = counts.iter().map(usize::to_string).collect();
+        writeln!(input, "{}x{}: {}", width, height, counts.join(" ")).unwrap();
+    }
+    input
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Presents;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input.lines())
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        part1(input)
+    }
+
+    fn generate(rng: &mut Rng, scale: usize) -> Option<String> {
+        Some(generate(rng, scale))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::Path;
+
     use super::*;
+    use crate::examples;
 
     #[test]
-    fn test_part1() {
+    fn test_examples() {
+        examples::assert_examples::<Day12>(include_str!("README.md"));
+    }
+
+    #[test]
+    fn test_part1_real_input() {
+        // Puzzle inputs aren't committed; with the real one saved next to this file, its
+        // known answer (also in answers.toml) must still come out
         let input_path = Path::new(file!()).parent().unwrap().join("input.txt");
-        let lines = utils::read_lines(&input_path);
-        
-        let result = part1(&lines);
-        assert_eq!(result, 403);
+        let Ok(input) = utils::try_read_input(&input_path) else {
+            return;
+        };
+        assert_eq!(part1(&Day12::parse(&input).unwrap()), 403);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_input("0:\n##\n#x\n##".lines()).err(), Some(ParseError::new(3, 2, "`#` or `.`", "`x`")));
+        assert_eq!(
+            parse_input("0:\n##".lines()).err(),
+            Some(ParseError::new(2, 3, "three rows of shape cells", "end of line"))
+        );
+        assert_eq!(
+            parse_input("0:\n##\n\n1:".lines()).err(),
+            Some(ParseError::new(2, 3, "three rows of shape cells", "end of line"))
+        );
+        assert_eq!(
+            parse_input("0:\n##\n##\n##\n4x4: 1 2".lines()).err(),
+            Some(ParseError::new(5, 8, "no presents of undefined shape 1", "`2`"))
+        );
+        assert_eq!(parse_input(["4by4: 1"]).err(), Some(ParseError::new(1, 1, "region dimensions `WxH`", "`4by4`")));
     }
 
     #[test]
-    fn test_part_1_small_example() {
-        let lines = vec![
-            "0:".to_string(),
-            "###".to_string(),
-            "##.".to_string(),
-            "##.".to_string(),
-            "".to_string(),
-            "1:".to_string(),
-            "###".to_string(),
-            "##.".to_string(),
-            ".##".to_string(),
-            "".to_string(),
-            "2:".to_string(),
-            ".##".to_string(),
-            "###".to_string(),
-            "##.".to_string(),
-            "".to_string(),
-            "3:".to_string(),
-            "##.".to_string(),
-            "###".to_string(),
-            "##.".to_string(),
-            "".to_string(),
-            "4:".to_string(),
-            "###".to_string(),
-            "#..".to_string(),
-            "###".to_string(),
-            "".to_string(),
-            "5:".to_string(),
-            "###".to_string(),
-            ".#.".to_string(),
-            "###".to_string(),
-            "".to_string(),
-            "4x4: 0 0 0 0 2 0".to_string(),
-            "12x5: 1 0 1 0 2 2".to_string(),
-            "12x5: 1 0 1 0 3 2".to_string(),
-        ];
-        
-        let result = part1(&lines);
-        assert_eq!(result, 2);
+    fn test_parse_streamed_lines() {
+        let (pattern_cells, grid_specs) = parse_input("0:\n#.\n##\n..\n\n\n1:\n###\n#.#\n###\n4x4: 1 0\n\n5x5: 0 2\n".lines()).unwrap();
+        assert_eq!(pattern_cells, HashMap::from([(0, 3), (1, 8)]));
+        let regions: Vec<_> = grid_specs.iter().map(|spec| (spec.width, spec.height, spec.pattern_counts.clone())).collect();
+        assert_eq!(regions, [(4, 4, vec![(0, 1)]), (5, 5, vec![(1, 2)])]);
     }
 }
@@ -0,0 +1,133 @@
+use crate::solution::{ParseError, Solution};
+
+const FENCE: &str = "```";
+
+/// Puzzle example embedded in a day's README as a fenced block whose info string holds
+/// the expected answers:
+///
+/// ````markdown
+/// ```example part1=3 part2=6
+/// L68
+/// L30
+/// ```
+/// ````
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// 1-based README line of the opening fence
+    pub line: usize,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Read every `example` block of a README, in order; other code blocks are ignored
+pub fn parse_examples(markdown: &str) -> Result<Vec<Example>, ParseError> {
+    let mut examples = Vec::new();
+    let mut lines = markdown.lines().enumerate();
+
+    while let Some((idx, line)) = lines.next() {
+        let Some(info) = line.trim().strip_prefix(FENCE) else {
+            continue;
+        };
+        let mut words = info.split_whitespace();
+        if words.next() != Some("example") {
+            // Skip over other blocks so their contents aren't mistaken for fences
+            lines.by_ref().find(|(_, line)| line.trim() == FENCE);
+            continue;
+        }
+
+        let mut example = Example { line: idx + 1, input: String::new(), part1: None, part2: None };
+        for word in words {
+            let slot = match word.split_once('=') {
+                Some(("part1", answer)) => (&mut example.part1, answer),
+                Some(("part2", answer)) => (&mut example.part2, answer),
+                _ => return Err(ParseError::at(idx + 1, line, word, "`part1=` or `part2=`")),
+            };
+            *slot.0 = Some(slot.1.to_string());
+        }
+
+        loop {
+            match lines.next() {
+                Some((_, line)) if line.trim() == FENCE => break,
+                Some((_, line)) => {
+                    example.input.push_str(line);
+                    example.input.push('\n');
+                }
+                None => {
+                    let last = markdown.lines().last().unwrap_or_default();
+                    let line_no = markdown.lines().count();
+                    return Err(ParseError::at(line_no, last, &last[last.len()..], "a closing ```"));
+                }
+            }
+        }
+        examples.push(example);
+    }
+
+    Ok(examples)
+}
+
+/// Solve every example in `markdown` with `S` and panic on the first answer that differs.
+/// Meant for tests: `assert_examples::<Day01>(include_str!("README.md"))`.
+pub fn assert_examples<S: Solution>(markdown: &str) {
+    let examples = parse_examples(markdown).unwrap_or_else(|err| panic!("README: {}", err));
+    assert!(!examples.is_empty(), "day {} README has no ```example blocks", S::DAY);
+
+    for example in examples {
+        let input = S::parse(&example.input)
+            .unwrap_or_else(|err| panic!("example at README line {}: {}", example.line, err));
+        if let Some(expected) = &example.part1 {
+            let answer = S::part1(&input).to_string();
+            assert_eq!(&answer, expected, "part 1 of the example at README line {}", example.line);
+        }
+        if let Some(expected) = &example.part2 {
+            let answer = S::part2(&input).map(|answer| answer.to_string());
+            assert_eq!(answer.as_ref(), Some(expected), "part 2 of the example at README line {}", example.line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const README: &str = "# Day 99
+
+```sh
+cargo test
+```
+
+```example part1=7
+1
+2
+```
+
+Text between blocks.
+
+```example part2=x
+```
+";
+
+    #[test]
+    fn test_parse_examples() {
+        let examples = parse_examples(README).unwrap();
+        assert_eq!(
+            examples,
+            [
+                Example { line: 7, input: "1\n2\n".to_string(), part1: Some("7".to_string()), part2: None },
+                Example { line: 14, input: String::new(), part1: None, part2: Some("x".to_string()) },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_examples("```example part3=1\n```\n"),
+            Err(ParseError::new(1, 12, "`part1=` or `part2=`", "`part3=1`"))
+        );
+        assert_eq!(
+            parse_examples("intro\n```example\n1\n"),
+            Err(ParseError::new(3, 2, "a closing ```", "end of line"))
+        );
+    }
+}
@@ -0,0 +1,469 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Signed integer usable as a point coordinate
+pub trait Coord:
+    Copy + Ord + fmt::Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, i128, isize);
+
+/// Point or vector in the plane; `y` grows downwards, like rows of a grid
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Point or vector in space
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Coord> Point2<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Square of the Euclidean distance, exact and with the same ordering as the distance itself
+    pub fn distance_squared(self, other: Self) -> T {
+        let d = self - other;
+        d.x * d.x + d.y * d.y
+    }
+
+    /// Neighbouring point one step in `dir`
+    pub fn step(self, dir: impl Into<Dir8>) -> Self {
+        self + dir.into().delta()
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    /// Square of the Euclidean distance, exact and with the same ordering as the distance itself
+    pub fn distance_squared(self, other: Self) -> T {
+        let d = self - other;
+        d.x * d.x + d.y * d.y + d.z * d.z
+    }
+}
+
+macro_rules! impl_point_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $point { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $point { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+
+        /// Scaling by a factor
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                $point { $($field: self.$field * factor),+ }
+            }
+        }
+    };
+}
+
+impl_point_ops!(Point2 { x, y });
+impl_point_ops!(Point3 { x, y, z });
+
+/// One of the four orthogonal directions, in clockwise order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// One of the eight orthogonal and diagonal directions, in clockwise order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// Quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Quarter turn counter-clockwise
+    pub fn turn_left(self) -> Self {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Unit vector; `Up` is negative `y`
+    pub fn delta<T: Coord>(self) -> Point2<T> {
+        Dir8::from(self).delta()
+    }
+
+    /// `(row, col)` offset for indexing a `Grid`
+    pub fn grid_offset(self) -> (isize, isize) {
+        Dir8::from(self).grid_offset()
+    }
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// Eighth of a turn clockwise
+    pub fn rotate_right(self) -> Self {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Eighth of a turn counter-clockwise
+    pub fn rotate_left(self) -> Self {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        Dir8::ALL[(self as usize + 2) % 8]
+    }
+
+    /// Quarter turn counter-clockwise
+    pub fn turn_left(self) -> Self {
+        Dir8::ALL[(self as usize + 6) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Unit vector (diagonals move one step on both axes); `Up` is negative `y`
+    pub fn delta<T: Coord>(self) -> Point2<T> {
+        let (row, col) = self.grid_offset();
+        let unit = |step: isize| match step {
+            -1 => -T::ONE,
+            1 => T::ONE,
+            _ => T::ZERO,
+        };
+        Point2::new(unit(col), unit(row))
+    }
+
+    /// `(row, col)` offset for indexing a `Grid`
+    pub fn grid_offset(self) -> (isize, isize) {
+        match self {
+            Dir8::Up => (-1, 0),
+            Dir8::UpRight => (-1, 1),
+            Dir8::Right => (0, 1),
+            Dir8::DownRight => (1, 1),
+            Dir8::Down => (1, 0),
+            Dir8::DownLeft => (1, -1),
+            Dir8::Left => (0, -1),
+            Dir8::UpLeft => (-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::ALL[dir as usize * 2]
+    }
+}
+
+/// Smallest axis-aligned rectangle holding a set of points, bounds included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds2<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+/// Smallest axis-aligned box holding a set of points, bounds included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds3<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T: Coord> Bounds2<T> {
+    /// Bounds of the rectangle with opposite corners `a` and `b`
+    pub fn spanning(a: Point2<T>, b: Point2<T>) -> Self {
+        Bounds2 { min: a, max: a }.including(b)
+    }
+
+    /// Bounds of `points`, or `None` if there are none
+    pub fn from_points(points: impl IntoIterator<Item = Point2<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Bounds2 { min: first, max: first }, |bounds, point| bounds.including(point)))
+    }
+
+    /// Bounds grown to also hold `point`
+    pub fn including(self, point: Point2<T>) -> Self {
+        Bounds2 {
+            min: Point2::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point2::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    pub fn contains(&self, point: Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// Number of columns covered, counting both edges
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    /// Number of rows covered, counting both edges
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    /// Corners in clockwise order, starting at `min`
+    pub fn corners(&self) -> [Point2<T>; 4] {
+        [
+            self.min,
+            Point2::new(self.max.x, self.min.y),
+            self.max,
+            Point2::new(self.min.x, self.max.y),
+        ]
+    }
+}
+
+impl<T: Coord> Bounds3<T> {
+    /// Bounds of the box with opposite corners `a` and `b`
+    pub fn spanning(a: Point3<T>, b: Point3<T>) -> Self {
+        Bounds3 { min: a, max: a }.including(b)
+    }
+
+    /// Bounds of `points`, or `None` if there are none
+    pub fn from_points(points: impl IntoIterator<Item = Point3<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Bounds3 { min: first, max: first }, |bounds, point| bounds.including(point)))
+    }
+
+    /// Bounds grown to also hold `point`
+    pub fn including(self, point: Point3<T>) -> Self {
+        Bounds3 {
+            min: Point3::new(self.min.x.min(point.x), self.min.y.min(point.y), self.min.z.min(point.z)),
+            max: Point3::new(self.max.x.max(point.x), self.max.y.max(point.y), self.max.z.max(point.z)),
+        }
+    }
+
+    pub fn contains(&self, point: Point3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// Number of points covered along each axis `(x, y, z)`, counting both faces
+    pub fn size(&self) -> Point3<T> {
+        self.max - self.min + Point3::new(T::ONE, T::ONE, T::ONE)
+    }
+
+    pub fn volume(&self) -> T {
+        let size = self.size();
+        size.x * size.y * size.z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let mut p = Point2::new(3, -2);
+        assert_eq!(p + Point2::new(1, 1), Point2::new(4, -1));
+        assert_eq!(p - Point2::new(1, 1), Point2::new(2, -3));
+        assert_eq!(-p, Point2::new(-3, 2));
+        assert_eq!(p * 3, Point2::new(9, -6));
+        p += Point2::new(1, 2);
+        p -= Point2::new(0, 1);
+        assert_eq!(p, Point2::new(4, -1));
+
+        let q = Point3::new(1i64, 2, 3);
+        assert_eq!(q + q * 2 - Point3::new(3, 3, 3), Point3::new(0, 3, 6));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point2::new(1i64, 1);
+        let b = Point2::new(4, -3);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.distance_squared(b), 25);
+
+        let c = Point3::new(162i64, 817, 812);
+        let d = Point3::new(425, 690, 689);
+        assert_eq!(c.manhattan(d), 263 + 127 + 123);
+        assert_eq!(c.chebyshev(d), 263);
+        assert_eq!(c.distance_squared(d), 263 * 263 + 127 * 127 + 123 * 123);
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.reverse(), Dir4::Right);
+        assert_eq!(Dir4::Up.delta::<i64>(), Point2::new(0, -1));
+        assert_eq!(Dir4::Left.grid_offset(), (0, -1));
+
+        assert_eq!(Dir8::Up.rotate_right(), Dir8::UpRight);
+        assert_eq!(Dir8::Up.rotate_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::UpRight.turn_right(), Dir8::DownRight);
+        assert_eq!(Dir8::DownLeft.reverse(), Dir8::UpRight);
+        assert_eq!(Dir8::DownLeft.delta::<i32>(), Point2::new(-1, 1));
+        assert!(Dir8::UpLeft.is_diagonal() && !Dir8::Down.is_diagonal());
+        assert_eq!(Dir8::from(Dir4::Down), Dir8::Down);
+
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(Dir8::from(dir).delta::<i64>(), dir.delta());
+        }
+        for dir in Dir8::ALL {
+            assert_eq!(dir.reverse().delta::<i64>(), -dir.delta::<i64>());
+        }
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let origin = Point2::new(0i64, 0);
+        assert_eq!(origin.step(Dir4::Right), Point2::new(1, 0));
+        assert_eq!(origin.step(Dir8::UpLeft), Point2::new(-1, -1));
+        assert_eq!(origin.neighbors4().filter(|p| p.manhattan(origin) == 1).count(), 4);
+        assert_eq!(origin.neighbors8().filter(|p| p.chebyshev(origin) == 1).count(), 8);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let points = [Point2::new(7i64, 1), Point2::new(2, 5), Point2::new(11, 3)];
+        let bounds = Bounds2::from_points(points).unwrap();
+        assert_eq!(bounds.min, Point2::new(2, 1));
+        assert_eq!(bounds.max, Point2::new(11, 5));
+        assert_eq!((bounds.width(), bounds.height(), bounds.area()), (10, 5, 50));
+        assert!(bounds.contains(Point2::new(2, 5)));
+        assert!(!bounds.contains(Point2::new(12, 3)));
+        assert_eq!(bounds.corners()[1], Point2::new(11, 1));
+        assert_eq!(Bounds2::<i64>::from_points([]), None);
+        assert_eq!(Bounds2::spanning(Point2::new(11, 1), Point2::new(2, 5)).area(), 50);
+
+        let cube = Bounds3::from_points([Point3::new(0i64, 0, 0), Point3::new(1, 2, 3)]).unwrap();
+        assert_eq!(cube.size(), Point3::new(2, 3, 4));
+        assert_eq!(cube.volume(), 24);
+        assert_eq!(Bounds3::spanning(Point3::new(1, 2, 3), Point3::new(0, 0, 0)), cube);
+        assert!(cube.contains(Point3::new(1, 1, 1)) && !cube.contains(Point3::new(0, 0, 4)));
+    }
+}
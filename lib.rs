@@ -0,0 +1,46 @@
+//! Advent of Code 2025 solutions
+//!
+//! Each `dayNN` module exposes its typed `parse_*`, `part1` and `part2` functions
+//! along with a `DayNN` implementation of [`solution::Solution`]. [`registry`]
+//! lists every day for the `aoc2025` runner built on top of this crate.
+
+pub mod answers;
+pub mod bench;
+#[cfg(test)]
+mod examples;
+pub mod geometry;
+#[cfg(test)]
+mod property;
+pub mod registry;
+pub mod rng;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod trace;
+pub mod utils;
+pub mod watch;
+
+#[path = "day01/solution.rs"]
+pub mod day01;
+#[path = "day02/solution.rs"]
+pub mod day02;
+#[path = "day03/solution.rs"]
+pub mod day03;
+#[path = "day04/solution.rs"]
+pub mod day04;
+#[path = "day05/solution.rs"]
+pub mod day05;
+#[path = "day06/solution.rs"]
+pub mod day06;
+#[path = "day07/solution.rs"]
+pub mod day07;
+#[path = "day08/solution.rs"]
+pub mod day08;
+#[path = "day09/solution.rs"]
+pub mod day09;
+#[path = "day10/solution.rs"]
+pub mod day10;
+#[path = "day11/solution.rs"]
+pub mod day11;
+#[path = "day12/solution.rs"]
+pub mod day12;
@@ -1,66 +1,847 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
 use std::time::Instant;
 
-// Include all day modules
-#[path = "day01/solution.rs"]
-mod day01;
-#[path = "day02/solution.rs"]
-mod day02;
-#[path = "day03/solution.rs"]
-mod day03;
-#[path = "day04/solution.rs"]
-mod day04;
-#[path = "day05/solution.rs"]
-mod day05;
-#[path = "day06/solution.rs"]
-mod day06;
-#[path = "day07/solution.rs"]
-mod day07;
-#[path = "day08/solution.rs"]
-mod day08;
-#[path = "day09/solution.rs"]
-mod day09;
-#[path = "day10/solution.rs"]
-mod day10;
-#[path = "day11/solution.rs"]
-mod day11;
-#[path = "day12/solution.rs"]
-mod day12;
-
-fn run_day(day: u8, runner: fn()) {
-    println!("\n{:=<50}", "");
-    println!("Day {:02}", day);
-    println!("{:-<50}", "");
-    
-    let start = Instant::now();
-    runner();
-    let duration = start.elapsed();
-    
-    println!("{:-<50}", "");
-    println!("Time: {:?}", duration);
-    println!("{:=<50}", "");
+use advent_of_code_2025::answers::{Answers, Verdict};
+use advent_of_code_2025::bench::{self, BenchConfig, Baseline};
+use advent_of_code_2025::runner::{self, DayError, Format};
+use advent_of_code_2025::solution::DynSolution;
+use advent_of_code_2025::rng::Rng;
+use advent_of_code_2025::watch::{self, RunAnswers, Watcher};
+use advent_of_code_2025::{registry, scaffold, utils};
+
+const USAGE: &str = "\
+Usage: aoc2025 [COMMAND] [--day <DAYS>] [--part <1|2>] [--input <PATH> | --inputs-dir <DIR>] [OPTIONS]
+
+Commands:
+  run                      Solve the selected days and print the answers [default]
+  verify                   Check the answers against the answers file; exits non-zero
+                           on a mismatch or an unreadable input
+  new                      Create `dayNN/` with a solution template and README for the
+                           day given with `--day`, and register it in registry.rs
+  bench                    Time parsing and each part over repeated runs and report
+                           min/median/p95/max per phase
+  gen                      Print a random input for the day given with `--day`; the
+                           same `--seed` always prints the same input
+  trace                    Print a step-by-step table of how the day given with `--day`
+                           reaches its answers, as `text` or `csv`
+  watch                    Re-run the tests and solution of the day given with `--day`
+                           whenever its source, examples or input change, and show
+                           how the answers moved
+
+Options:
+  -d, --day <DAYS>         Days to run: a single day (7), an inclusive range (3..6)
+                           or a comma-separated list of both (1,3..5,9)
+  -p, --part <PART>        Run only part 1 or part 2 of each selected day
+  -i, --input <PATH>       Input file for the selected day; `-` reads standard input
+      --inputs-dir <DIR>   Root holding `dayNN/input.txt` for every day [default: .]
+  -f, --format <FORMAT>    Output as decorated `text`, a `json` array, or `ndjson`
+                           records (one per day and part); `trace` takes `text` or
+                           `csv` [default: text]
+  -j, --jobs <N>           Run up to N days at once on N threads, with the two parts of
+                           a day side by side while a thread is idle; output stays
+                           in day order (not used by `bench`) [default: 1]
+      --answers <PATH>     Known answers for `verify` [default: <inputs-dir>/answers.toml]
+      --warmup <N>         Untimed runs of each phase before `bench` measures [default: 3]
+  -n, --iterations <N>     Timed runs of each phase for `bench` [default: 20]
+      --save-baseline <PATH>
+                           Write the `bench` timings to PATH for later comparison
+      --baseline <PATH>    Compare `bench` medians with timings saved earlier
+      --size <N>           Make the `gen` input about N times as large as a real
+                           one [default: 1]
+      --seed <N>           Seed for `gen` [default: 1]
+  -h, --help               Print this help";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Run,
+    Verify,
+    Bench,
+    New,
+    Gen,
+    Watch,
+    Trace,
 }
 
-fn main() {
-    println!("\n🎄 Advent of Code 2025 🎄\n");
-    
+impl Command {
+    fn name(self) -> &'static str {
+        match self {
+            Command::Run => "run",
+            Command::Verify => "verify",
+            Command::Bench => "bench",
+            Command::New => "new",
+            Command::Gen => "gen",
+            Command::Watch => "watch",
+            Command::Trace => "trace",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    command: Command,
+    days: Vec<u8>,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    inputs_dir: PathBuf,
+    format: Format,
+    jobs: usize,
+    answers: Option<PathBuf>,
+    bench: BenchConfig,
+    baseline: Option<PathBuf>,
+    save_baseline: Option<PathBuf>,
+    size: usize,
+    seed: u64,
+}
+
+impl Options {
+    /// Input file for a day: the `--input` override, else `<inputs-dir>/dayNN/input.txt`
+    fn input_path(&self, day: u8) -> PathBuf {
+        match &self.input {
+            Some(path) => path.clone(),
+            None => self.inputs_dir.join(format!("day{:02}", day)).join("input.txt"),
+        }
+    }
+
+    /// The selected days with their input files, in day order
+    fn selected(&self) -> Vec<(&'static dyn DynSolution, PathBuf)> {
+        self.days
+            .iter()
+            .filter_map(|&day| Some((registry::find(day)?, self.input_path(day))))
+            .collect()
+    }
+
+    fn answers_path(&self) -> PathBuf {
+        self.answers.clone().unwrap_or_else(|| self.inputs_dir.join("answers.toml"))
+    }
+
+    fn parts(&self) -> Vec<u8> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        }
+    }
+}
+
+fn parse_day_number(value: &str) -> Result<u8, String> {
+    let number: u8 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid day `{}`", value))?;
+    if registry::find(number).is_none() {
+        let last = registry::SOLUTIONS.last().map_or(0, |solution| solution.day());
+        return Err(format!("day {} is not implemented (available: 1..{})", number, last));
+    }
+    Ok(number)
+}
+
+/// Parse a day selection such as `7`, `3..6`, `3..=6` or `1,3..5,9`
+/// Ranges are inclusive; the result is sorted and deduplicated
+fn parse_day_spec(spec: &str) -> Result<Vec<u8>, String> {
+    let mut days = Vec::new();
+
+    for item in spec.split(',') {
+        if let Some((start, end)) = item.split_once("..") {
+            let start = parse_day_number(start)?;
+            let end = parse_day_number(end.strip_prefix('=').unwrap_or(end))?;
+            if start > end {
+                return Err(format!("empty day range `{}`", item));
+            }
+            days.extend(start..=end);
+        } else {
+            days.push(parse_day_number(item)?);
+        }
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+/// Day for `new`: a single puzzle day that isn't implemented yet
+fn parse_new_day(value: &str) -> Result<u8, String> {
+    let number: u8 = value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|day| (1..=scaffold::LAST_DAY).contains(day))
+        .ok_or_else(|| format!("invalid day `{}` (expected 1..{})", value, scaffold::LAST_DAY))?;
+    if registry::find(number).is_some() {
+        return Err(format!("day {} is already implemented", number));
+    }
+    Ok(number)
+}
+
+fn parse_part(value: &str) -> Result<u8, String> {
+    match value.trim() {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part `{}` (expected 1 or 2)", value)),
+    }
+}
+
+fn parse_count(value: &str, name: &str, min: usize) -> Result<usize, String> {
+    match value.trim().parse() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(format!("invalid {} `{}` (expected a whole number of at least {})", name, value, min)),
+    }
+}
+
+fn parse_args<I>(args: I) -> Result<Options, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut day_spec = None;
+    let mut part = None;
+    let mut input = None;
+    let mut inputs_dir = None;
+    let mut format = Format::Text;
+    let mut jobs = 1;
+    let mut answers = None;
+    let mut bench = BenchConfig::default();
+    let mut baseline = None;
+    let mut save_baseline = None;
+    let mut size = 1;
+    let mut seed = 1;
+    let mut args = args.into_iter().peekable();
+
+    let command = match args.peek().map(String::as_str) {
+        Some("run") => Command::Run,
+        Some("verify") => Command::Verify,
+        Some("bench") => Command::Bench,
+        Some("new") => Command::New,
+        Some("gen") => Command::Gen,
+        Some("watch") => Command::Watch,
+        Some("trace") => Command::Trace,
+        Some(other) if !other.starts_with('-') => return Err(format!("unknown command `{}`", other)),
+        _ => Command::Run,
+    };
+    if args.peek().is_some_and(|arg| !arg.starts_with('-')) {
+        args.next();
+    }
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg, None),
+        };
+        let mut value = |name: &str| {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("missing value for `{}`", name))
+        };
+
+        match flag.as_str() {
+            "-d" | "--day" => day_spec = Some(value("--day")?),
+            "-p" | "--part" => part = Some(parse_part(&value("--part")?)?),
+            "-i" | "--input" => input = Some(PathBuf::from(value("--input")?)),
+            "--inputs-dir" => inputs_dir = Some(PathBuf::from(value("--inputs-dir")?)),
+            "-f" | "--format" => format = value("--format")?.parse()?,
+            "-j" | "--jobs" => jobs = parse_count(&value("--jobs")?, "job count", 1)?,
+            "--answers" => answers = Some(PathBuf::from(value("--answers")?)),
+            "--warmup" => bench.warmup = parse_count(&value("--warmup")?, "warm-up count", 0)?,
+            "-n" | "--iterations" => bench.iterations = parse_count(&value("--iterations")?, "iteration count", 1)?,
+            "--baseline" => baseline = Some(PathBuf::from(value("--baseline")?)),
+            "--save-baseline" => save_baseline = Some(PathBuf::from(value("--save-baseline")?)),
+            "--size" => size = parse_count(&value("--size")?, "size", 1)?,
+            "--seed" => {
+                let text = value("--seed")?;
+                seed = text.trim().parse().map_err(|_| format!("invalid seed `{}` (expected a whole number)", text))?;
+            }
+            _ => return Err(format!("unexpected argument `{}`", flag)),
+        }
+    }
+
+    let days = match (command, day_spec) {
+        (Command::New, Some(spec)) => vec![parse_new_day(&spec)?],
+        (Command::New, None) => return Err("`new` requires the day to create with `--day`".to_string()),
+        (_, Some(spec)) => parse_day_spec(&spec)?,
+        (_, None) => registry::SOLUTIONS.iter().map(|solution| solution.day()).collect(),
+    };
+    if matches!(command, Command::Gen | Command::Watch | Command::Trace) && days.len() != 1 {
+        return Err(format!("`{}` requires exactly one day to be selected with `--day`", command.name()));
+    }
+    if format == Format::Csv && command != Command::Trace {
+        return Err(format!("`--format csv` only works with `trace`, not `{}`", command.name()));
+    }
+    if command == Command::Trace && matches!(format, Format::Json | Format::Ndjson) {
+        return Err("`trace` prints `text` or `csv`".to_string());
+    }
+    if command == Command::Watch && input.as_deref() == Some(Path::new("-")) {
+        return Err("`watch` cannot read its input from standard input".to_string());
+    }
+    if input.is_some() {
+        if inputs_dir.is_some() {
+            return Err("`--input` and `--inputs-dir` cannot be combined".to_string());
+        }
+        if days.len() != 1 {
+            return Err("`--input` requires exactly one day to be selected with `--day`".to_string());
+        }
+    }
+
+    Ok(Options {
+        command,
+        days,
+        part,
+        input,
+        inputs_dir: inputs_dir.unwrap_or_default(),
+        format,
+        jobs,
+        answers,
+        bench,
+        baseline,
+        save_baseline,
+        size,
+        seed,
+    })
+}
+
+fn load_answers(path: &Path) -> Option<Answers> {
+    let text = match utils::try_read_input(path) {
+        Ok(text) => text,
+        Err(error) if error.source.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!("warning: no answers file at {}; every answer is MISSING", utils::input_name(path));
+            return Some(Answers::default());
+        }
+        Err(error) => {
+            eprintln!("error: cannot read answers: {}", error);
+            return None;
+        }
+    };
+    match Answers::parse(&text) {
+        Ok(answers) => Some(answers),
+        Err(error) => {
+            eprint!("{}", runner::format_diagnostic(&error, path, &text));
+            None
+        }
+    }
+}
+
+/// Solve every selected day and print the answers; returns false if any day failed
+fn run(options: &Options) -> bool {
+    if options.format == Format::Text {
+        println!("\n🎄 Advent of Code 2025 🎄\n");
+    }
+
     let total_start = Instant::now();
-    
-    run_day(1, day01::main);
-    run_day(2, day02::main);
-    run_day(3, day03::main);
-    run_day(4, day04::main);
-    run_day(5, day05::main);
-    run_day(6, day06::main);
-    run_day(7, day07::main);
-    run_day(8, day08::main);
-    run_day(9, day09::main);
-    run_day(10, day10::main);
-    run_day(11, day11::main);
-    run_day(12, day12::main);
-    
+    let mut failed = false;
+    let mut records = Vec::new();
+
+    runner::run_days(&options.selected(), &options.parts(), options.jobs, |report| {
+        failed |= report.failed();
+
+        match options.format {
+            Format::Text => runner::print_text(&report, options.part.is_some()),
+            Format::Json => records.extend(runner::json_records(&report)),
+            Format::Ndjson => {
+                for record in runner::json_records(&report) {
+                    println!("{}", record);
+                }
+            }
+            Format::Csv => unreachable!("`--format csv` is rejected for `run`"),
+        }
+    });
+
     let total_duration = total_start.elapsed();
-    
-    println!("\n{:=<50}", "");
-    println!("Total time: {:?}", total_duration);
-    println!("{:=<50}", "");
+
+    match options.format {
+        Format::Text => {
+            println!("\n{:=<50}", "");
+            println!("Total time: {:?}", total_duration);
+            println!("{:=<50}", "");
+        }
+        Format::Json => println!("[\n  {}\n]", records.join(",\n  ")),
+        Format::Ndjson | Format::Csv => {}
+    }
+
+    !failed
+}
+
+/// Compare every selected day and part with the known answers; returns false on any FAIL or ERROR
+fn verify(options: &Options) -> bool {
+    let Some(answers) = load_answers(&options.answers_path()) else {
+        return false;
+    };
+    let (mut passed, mut failed, mut missing, mut errors) = (0, 0, 0, 0);
+
+    runner::run_days(&options.selected(), &options.parts(), options.jobs, |report| {
+        let number = report.day;
+        if let Some(error) = &report.error {
+            let message = match error {
+                DayError::Read(error) => error.to_string(),
+                DayError::Parse(error) => format!("{}: {}", utils::input_name(&report.input_path), error),
+                DayError::Panic(message) => format!("parsing panicked: {}", message),
+            };
+            println!("Day {:02}         ERROR    {}", number, message);
+            errors += 1;
+            return;
+        }
+
+        for part in &report.parts {
+            if let Some(message) = &part.panic {
+                println!("Day {:02} part {}  ERROR    panicked: {}", number, part.part, message);
+                errors += 1;
+                continue;
+            }
+            let Some(answer) = &part.answer else {
+                continue;
+            };
+            let verdict = answers.verdict(number, part.part, answer);
+            match &verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Fail { .. } => failed += 1,
+                Verdict::Missing => missing += 1,
+            }
+            let detail = match &verdict {
+                Verdict::Fail { expected } => format!("{} (expected {})", answer, expected),
+                _ => answer.clone(),
+            };
+            println!("Day {:02} part {}  {:<8} {}", number, part.part, verdict, detail);
+        }
+    });
+
+    println!(
+        "\n{} passed, {} failed, {} missing, {} errors",
+        passed, failed, missing, errors
+    );
+    failed == 0 && errors == 0
+}
+
+fn load_baseline(path: &Path) -> Option<Baseline> {
+    let text = match utils::try_read_input(path) {
+        Ok(text) => text,
+        Err(error) => {
+            eprintln!("error: cannot read baseline: {}", error);
+            return None;
+        }
+    };
+    match Baseline::parse(&text) {
+        Ok(baseline) => Some(baseline),
+        Err(error) => {
+            eprint!("{}", runner::format_diagnostic(&error, path, &text));
+            None
+        }
+    }
+}
+
+/// Benchmark every selected day, optionally comparing with and saving a baseline
+/// Returns false if an input, the baseline or the saved file couldn't be handled
+fn bench(options: &Options) -> bool {
+    let baseline = match &options.baseline {
+        Some(path) => match load_baseline(path) {
+            Some(baseline) => Some(baseline),
+            None => return false,
+        },
+        None => None,
+    };
+    let mut ok = true;
+    let mut results = Vec::new();
+
+    println!(
+        "{} warm-up and {} timed runs per phase\n",
+        options.bench.warmup, options.bench.iterations
+    );
+    bench::print_header(baseline.is_some());
+
+    for &number in &options.days {
+        let Some(solution) = registry::find(number) else {
+            continue;
+        };
+        let input_path = options.input_path(number);
+        let input = match utils::try_map_input(&input_path) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("error: cannot read input: {}", error);
+                ok = false;
+                continue;
+            }
+        };
+
+        match bench::bench_day(solution, &input, &options.parts(), options.bench) {
+            Ok(phases) => {
+                for phase in &phases {
+                    bench::print_row(phase, baseline.as_ref());
+                }
+                results.extend(phases);
+            }
+            Err(error) => {
+                eprint!("{}", runner::format_diagnostic(&error, &input_path, &input));
+                ok = false;
+            }
+        }
+    }
+
+    if let Some(path) = &options.save_baseline {
+        match std::fs::write(path, bench::format_baseline(&results)) {
+            Ok(()) => println!("\nSaved baseline to {}", path.display()),
+            Err(error) => {
+                eprintln!("error: cannot save baseline to {}: {}", path.display(), error);
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+/// Scaffold the day selected for `new` in the source tree this binary was built from
+fn new_day(options: &Options) -> bool {
+    let day = options.days[0];
+    match scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), day) {
+        Ok(files) => {
+            for file in files {
+                println!("wrote {}", file.display());
+            }
+            println!("\nRebuild to run it: cargo run -- --day {}", day);
+            true
+        }
+        Err(message) => {
+            eprintln!("error: {}", message);
+            false
+        }
+    }
+}
+
+/// Print a generated input for the day selected for `gen`
+fn generate(options: &Options) -> bool {
+    let day = options.days[0];
+    let Some(solution) = registry::find(day) else {
+        return false;
+    };
+    let Some(input) = solution.generate(&mut Rng::new(options.seed), options.size) else {
+        eprintln!("error: day {} has no input generator", day);
+        return false;
+    };
+    match io::stdout().lock().write_all(input.as_bytes()) {
+        Ok(()) => true,
+        // Piping into `head` and the like closes the output early; that isn't a failure
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => true,
+        Err(error) => {
+            eprintln!("error: cannot write the input: {}", error);
+            false
+        }
+    }
+}
+
+/// Print the selected day's trace of its answers for the input
+fn trace(options: &Options) -> bool {
+    let day = options.days[0];
+    let Some(solution) = registry::find(day) else {
+        return false;
+    };
+    let input_path = options.input_path(day);
+    let input = match utils::try_map_input(&input_path) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("error: cannot read input: {}", error);
+            return false;
+        }
+    };
+    let parsed = match solution.parse(&input) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            eprint!("{}", runner::format_diagnostic(&error, &input_path, &input));
+            return false;
+        }
+    };
+
+    match parsed.trace() {
+        Some(trace) if options.format == Format::Csv => print!("{}", trace.to_csv()),
+        Some(trace) => print!("{}", trace.to_text()),
+        None => {
+            eprintln!("error: day {} has no trace", day);
+            return false;
+        }
+    }
+    true
+}
+
+/// `cargo` in the source tree this binary was built from
+fn cargo(args: &[&str]) -> process::Command {
+    let mut command = process::Command::new(option_env!("CARGO").unwrap_or("cargo"));
+    command.current_dir(env!("CARGO_MANIFEST_DIR")).args(args);
+    command
+}
+
+/// Rebuild and run one day's tests and solution; `None` if the solution didn't build
+fn rerun_day(options: &Options, day: u8, input_path: &Path) -> Option<RunAnswers> {
+    let module = format!("day{:02}::", day);
+    // Passing test output is noise here; only a failure is worth the screen space
+    match cargo(&["test", "--quiet", "--lib", &module]).output() {
+        Ok(output) if output.status.success() => println!("tests: ok"),
+        Ok(output) => {
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            println!("tests: FAILED");
+        }
+        Err(error) => println!("tests: cannot run cargo: {}", error),
+    }
+
+    let day = day.to_string();
+    let input = input_path.to_string_lossy();
+    let mut args = vec!["run", "--quiet"];
+    // Match the profile of this binary, so `cargo run --release -- watch` times release code
+    if !cfg!(debug_assertions) {
+        args.push("--release");
+    }
+    args.extend(["--", "run", "--day", &day, "--input", &input, "--format", "ndjson"]);
+    let part = options.part.map(|part| part.to_string());
+    if let Some(part) = &part {
+        args.extend(["--part", part]);
+    }
+
+    let output = cargo(&args).stderr(process::Stdio::inherit()).output();
+    match output {
+        Ok(output) if output.status.success() || !output.stdout.is_empty() => {
+            Some(watch::parse_ndjson(&String::from_utf8_lossy(&output.stdout)))
+        }
+        Ok(_) => None,
+        Err(error) => {
+            println!("cannot run cargo: {}", error);
+            None
+        }
+    }
+}
+
+/// Poll the selected day's files and re-run it after every change, until interrupted
+fn watch_day(options: &Options) -> bool {
+    let day = options.days[0];
+    let input_path = options.input_path(day);
+    let input_path = std::path::absolute(&input_path).unwrap_or(input_path);
+    let mut watcher = Watcher::new(watch::day_files(Path::new(env!("CARGO_MANIFEST_DIR")), day, &input_path));
+
+    println!("Watching day {} (Ctrl-C to stop):", day);
+    for path in watcher.paths() {
+        println!("  {}", path.display());
+    }
+
+    let mut previous: Option<RunAnswers> = None;
+    loop {
+        println!();
+        match rerun_day(options, day, &input_path) {
+            Some(answers) => {
+                for line in watch::diff_answers(previous.as_ref(), &answers) {
+                    println!("{}", line);
+                }
+                previous = Some(answers);
+            }
+            None => println!("solution: build failed; answers kept from the last good run"),
+        }
+
+        let changed = loop {
+            thread::sleep(watch::POLL_INTERVAL);
+            let changed = watcher.changed();
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+        let names: Vec<String> = changed.iter().map(|path| path.display().to_string()).collect();
+        println!("\nchanged: {}", names.join(", "));
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let ok = match options.command {
+        Command::Run => run(&options),
+        Command::Verify => verify(&options),
+        Command::Bench => bench(&options),
+        Command::New => new_day(&options),
+        Command::Gen => generate(&options),
+        Command::Watch => watch_day(&options),
+        Command::Trace => trace(&options),
+    };
+    if !ok {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_day_spec() {
+        assert_eq!(parse_day_spec("7"), Ok(vec![7]));
+        assert_eq!(parse_day_spec("3..6"), Ok(vec![3, 4, 5, 6]));
+        assert_eq!(parse_day_spec("3..=6"), Ok(vec![3, 4, 5, 6]));
+        assert_eq!(parse_day_spec("1,9"), Ok(vec![1, 9]));
+        assert_eq!(parse_day_spec("9,1..3,2"), Ok(vec![1, 2, 3, 9]));
+    }
+
+    #[test]
+    fn test_parse_day_spec_errors() {
+        assert!(parse_day_spec("26").unwrap_err().contains("not implemented"));
+        assert!(parse_day_spec("0").is_err());
+        assert!(parse_day_spec("six").is_err());
+        assert!(parse_day_spec("6..3").is_err());
+        assert!(parse_day_spec("1,").is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(args(&["--day", "7", "--part", "2"])).unwrap();
+        assert_eq!(options.days, vec![7]);
+        assert_eq!(options.part, Some(2));
+
+        let options = parse_args(args(&["--day=1,9"])).unwrap();
+        assert_eq!(options.days, vec![1, 9]);
+        assert_eq!(options.part, None);
+
+        let options = parse_args(args(&[])).unwrap();
+        let all: Vec<u8> = registry::SOLUTIONS.iter().map(|solution| solution.day()).collect();
+        assert_eq!(options.days, all);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(args(&["--part", "3"])).is_err());
+        assert!(parse_args(args(&["--day"])).is_err());
+        assert!(parse_args(args(&["--frobnicate"])).is_err());
+        assert!(parse_args(args(&["--format", "yaml"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_args(args(&[])).unwrap().command, Command::Run);
+        assert_eq!(parse_args(args(&["run", "-d", "3"])).unwrap().command, Command::Run);
+
+        let options = parse_args(args(&["verify", "--day", "1..3"])).unwrap();
+        assert_eq!(options.command, Command::Verify);
+        assert_eq!(options.days, vec![1, 2, 3]);
+        assert_eq!(options.answers_path(), Path::new("answers.toml"));
+
+        let options = parse_args(args(&["verify", "--inputs-dir", "in", "--answers", "a.toml"])).unwrap();
+        assert_eq!(options.answers_path(), Path::new("a.toml"));
+        let options = parse_args(args(&["verify", "--inputs-dir", "in"])).unwrap();
+        assert_eq!(options.answers_path(), Path::new("in/answers.toml"));
+
+        assert!(parse_args(args(&["frobnicate"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench_options() {
+        let options = parse_args(args(&["bench", "-d", "4"])).unwrap();
+        assert_eq!(options.command, Command::Bench);
+        assert_eq!(options.bench, BenchConfig::default());
+        assert_eq!((options.baseline, options.save_baseline), (None, None));
+
+        let options = parse_args(args(&[
+            "bench", "--warmup=0", "-n", "50", "--baseline", "old.txt", "--save-baseline", "new.txt",
+        ]))
+        .unwrap();
+        assert_eq!(options.bench, BenchConfig { warmup: 0, iterations: 50 });
+        assert_eq!(options.baseline, Some(PathBuf::from("old.txt")));
+        assert_eq!(options.save_baseline, Some(PathBuf::from("new.txt")));
+
+        assert!(parse_args(args(&["bench", "-n", "0"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_new() {
+        // The first day without a solution, so the test keeps working as days are added
+        if let Some(day) = (1..=scaffold::LAST_DAY).find(|&day| registry::find(day).is_none()) {
+            let options = parse_args(args(&["new", "--day", &day.to_string()])).unwrap();
+            assert_eq!(options.command, Command::New);
+            assert_eq!(options.days, vec![day]);
+            assert!(parse_args(args(&["-d", &day.to_string()])).is_err());
+        }
+
+        assert!(parse_args(args(&["new"])).is_err());
+        assert!(parse_args(args(&["new", "-d", "3"])).unwrap_err().contains("already implemented"));
+        assert!(parse_args(args(&["new", "-d", "26"])).is_err());
+        assert!(parse_args(args(&["new", "-d", "24,25"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_gen() {
+        let options = parse_args(args(&["gen", "--day", "8"])).unwrap();
+        assert_eq!(options.command, Command::Gen);
+        assert_eq!((options.days, options.size, options.seed), (vec![8], 1, 1));
+
+        let options = parse_args(args(&["gen", "-d", "3", "--size=100", "--seed", "42"])).unwrap();
+        assert_eq!((options.size, options.seed), (100, 42));
+
+        assert!(parse_args(args(&["gen"])).is_err());
+        assert!(parse_args(args(&["gen", "-d", "1..3"])).is_err());
+        assert!(parse_args(args(&["gen", "-d", "1", "--size", "0"])).is_err());
+        assert!(parse_args(args(&["gen", "-d", "1", "--seed", "-1"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_trace() {
+        let options = parse_args(args(&["trace", "--day", "1", "--format", "csv"])).unwrap();
+        assert_eq!((options.command, options.format), (Command::Trace, Format::Csv));
+        assert_eq!(parse_args(args(&["trace", "-d", "1"])).unwrap().format, Format::Text);
+
+        assert!(parse_args(args(&["trace"])).is_err());
+        assert!(parse_args(args(&["trace", "-d", "1", "-f", "json"])).is_err());
+        assert!(parse_args(args(&["-d", "1", "-f", "csv"])).unwrap_err().contains("only works with `trace`"));
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        let options = parse_args(args(&["watch", "--day", "4", "--input", "big.txt"])).unwrap();
+        assert_eq!(options.command, Command::Watch);
+        assert_eq!(options.input_path(4), PathBuf::from("big.txt"));
+
+        assert!(parse_args(args(&["watch"])).unwrap_err().contains("exactly one day"));
+        assert!(parse_args(args(&["watch", "-d", "4", "-i", "-"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_jobs() {
+        assert_eq!(parse_args(args(&[])).unwrap().jobs, 1);
+        assert_eq!(parse_args(args(&["-j", "4"])).unwrap().jobs, 4);
+        assert_eq!(parse_args(args(&["verify", "--jobs=12"])).unwrap().jobs, 12);
+        assert!(parse_args(args(&["--jobs", "0"])).is_err());
+        assert!(parse_args(args(&["--jobs", "many"])).is_err());
+        assert!(parse_args(args(&["bench", "--warmup", "-1"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(parse_args(args(&[])).unwrap().format, Format::Text);
+        assert_eq!(parse_args(args(&["--format", "json"])).unwrap().format, Format::Json);
+        assert_eq!(parse_args(args(&["-f", "ndjson"])).unwrap().format, Format::Ndjson);
+    }
+
+    #[test]
+    fn test_input_paths() {
+        let options = parse_args(args(&["--day", "3"])).unwrap();
+        assert_eq!(options.input_path(3), Path::new("day03/input.txt"));
+
+        let options = parse_args(args(&["--inputs-dir", "/data/aoc"])).unwrap();
+        assert_eq!(options.input_path(11), Path::new("/data/aoc/day11/input.txt"));
+
+        let options = parse_args(args(&["--day", "7", "--input", "-"])).unwrap();
+        assert_eq!(options.input_path(7), Path::new("-"));
+    }
+
+    #[test]
+    fn test_input_requires_single_day() {
+        assert!(parse_args(args(&["--input", "stress.txt"])).is_err());
+        assert!(parse_args(args(&["--day", "1..2", "--input", "stress.txt"])).is_err());
+        assert!(parse_args(args(&["--day", "1", "--input", "a", "--inputs-dir", "b"])).is_err());
+    }
 }
@@ -0,0 +1,165 @@
+use std::env;
+use std::fmt::Debug;
+
+use crate::rng::Rng;
+use crate::utils;
+
+/// Generated cases per property, unless `AOC2025_CASES` says otherwise
+const DEFAULT_CASES: usize = 256;
+/// Upper bound on candidates tried while shrinking one counterexample
+const MAX_SHRINK_STEPS: usize = 10_000;
+
+/// Value that can propose simpler variants of itself, most aggressive first
+pub trait Shrink: Sized {
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! impl_shrink_int {
+    ($($ty:ty),+) => {$(
+        impl Shrink for $ty {
+            #[allow(unused_comparisons)]
+            fn shrink(&self) -> Vec<Self> {
+                let value = *self;
+                let mut candidates = Vec::new();
+                // Negative values first try their positive counterpart, then move toward zero
+                for candidate in [0, value / 2, value - value.signum(), value.wrapping_neg()] {
+                    let simpler = candidate >= 0 || candidate > value;
+                    if candidate != value && simpler && !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+                candidates
+            }
+        }
+    )+};
+}
+
+impl_shrink_int!(i32, i64);
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        if self.is_empty() {
+            return candidates;
+        }
+        candidates.push(Vec::new());
+        if self.len() > 2 {
+            let half = self.len() / 2;
+            candidates.push(self[..half].to_vec());
+            candidates.push(self[half..].to_vec());
+        }
+        for idx in 0..self.len() {
+            let mut without = self.clone();
+            without.remove(idx);
+            candidates.push(without);
+        }
+        for (idx, item) in self.iter().enumerate() {
+            for simpler in item.shrink() {
+                let mut candidate = self.clone();
+                candidate[idx] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let firsts = self.0.shrink().into_iter().map(|a| (a, self.1.clone()));
+        let seconds = self.1.shrink().into_iter().map(|b| (self.0.clone(), b));
+        firsts.chain(seconds).collect()
+    }
+}
+
+fn env_number(name: &str) -> Option<u64> {
+    env::var(name).ok().and_then(|value| value.parse().ok())
+}
+
+/// Seed for a property: `AOC2025_SEED` if set, otherwise derived from its name so every
+/// run is reproducible but properties don't share a stream
+fn seed_for(name: &str) -> u64 {
+    env_number("AOC2025_SEED").unwrap_or_else(|| utils::fnv1a(name.as_bytes()))
+}
+
+/// Greedily replace `input` with simpler variants for as long as they still fail
+pub fn shrink<T: Shrink>(mut input: T, fails: impl Fn(&T) -> bool) -> T {
+    let mut steps = 0;
+    'simplify: while steps < MAX_SHRINK_STEPS {
+        for candidate in input.shrink() {
+            steps += 1;
+            if fails(&candidate) {
+                input = candidate;
+                continue 'simplify;
+            }
+        }
+        break;
+    }
+    input
+}
+
+/// Check `fast` against the obviously correct `naive` on generated inputs. On a mismatch
+/// the input is shrunk and the test panics with the smallest counterexample found.
+pub fn assert_same<T, R>(name: &str, generate: impl Fn(&mut Rng) -> T, fast: impl Fn(&T) -> R, naive: impl Fn(&T) -> R)
+where
+    T: Shrink + Debug,
+    R: PartialEq + Debug,
+{
+    let seed = seed_for(name);
+    let cases = env_number("AOC2025_CASES").map_or(DEFAULT_CASES, |cases| cases as usize);
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        let input = generate(&mut rng);
+        if fast(&input) != naive(&input) {
+            let minimal = shrink(input, |input| fast(input) != naive(input));
+            panic!(
+                "{}: fast and naive disagree on case {} (AOC2025_SEED={})\n  input: {:?}\n  fast:  {:?}\n  naive: {:?}",
+                name,
+                case,
+                seed,
+                minimal,
+                fast(&minimal),
+                naive(&minimal)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrink_ints() {
+        assert_eq!(10i64.shrink(), [0, 5, 9]);
+        assert_eq!((-6i64).shrink(), [0, -3, -5, 6]);
+        assert_eq!(1i64.shrink(), [0]);
+        assert!(0i64.shrink().is_empty());
+    }
+
+    #[test]
+    fn test_shrink_finds_minimal_counterexample() {
+        // Pretend the fast path mishandles any element of at least 10
+        let sum_is_wrong = |values: &Vec<i64>| values.iter().any(|&value| value >= 10);
+        let input = vec![3, -40, 27, 8, 15, 99];
+        assert_eq!(shrink(input, sum_is_wrong), [10]);
+
+        let pair_is_wrong = |&(a, b): &(i64, i64)| a > 3 && b < -2;
+        assert_eq!(shrink((50, -70), pair_is_wrong), (4, -3));
+    }
+
+    #[test]
+    fn test_assert_same_reports_shrunk_input() {
+        let result = std::panic::catch_unwind(|| {
+            assert_same(
+                "buggy max",
+                |rng| (0..8).map(|_| rng.range(-100..=100)).collect::<Vec<i64>>(),
+                |values| values.iter().copied().filter(|&value| value != 42).max(),
+                |values| values.iter().copied().max(),
+            )
+        });
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("input: [42]"), "{}", message);
+    }
+}
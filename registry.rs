@@ -0,0 +1,65 @@
+use crate::solution::DynSolution;
+
+/// Every implemented day, in day order
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &crate::day01::Day01,
+    &crate::day02::Day02,
+    &crate::day03::Day03,
+    &crate::day04::Day04,
+    &crate::day05::Day05,
+    &crate::day06::Day06,
+    &crate::day07::Day07,
+    &crate::day08::Day08,
+    &crate::day09::Day09,
+    &crate::day10::Day10,
+    &crate::day11::Day11,
+    &crate::day12::Day12,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.iter().copied().find(|solution| solution.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        let days: Vec<u8> = SOLUTIONS.iter().map(|solution| solution.day()).collect();
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(find(7).map(|solution| solution.day()), Some(7));
+        assert!(find(0).is_none());
+    }
+
+    #[test]
+    fn test_parse_and_solve_through_registry() {
+        let parsed = find(1).unwrap().parse("L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n").unwrap();
+        assert_eq!(parsed.part1(), "3");
+        assert_eq!(parsed.part2(), Some("6".to_string()));
+        assert_eq!(find(12).unwrap().parse("").unwrap().part2(), None);
+    }
+
+    #[test]
+    fn test_generators_are_seeded_and_parse() {
+        for solution in SOLUTIONS {
+            let generate = |seed| solution.generate(&mut crate::rng::Rng::new(seed), 1);
+            // Generators are opt-in, so a day without one has nothing to check
+            let Some(input) = generate(5) else {
+                assert_eq!(generate(6), None, "day {}", solution.day());
+                continue;
+            };
+            assert_eq!(Some(&input), generate(5).as_ref(), "day {}", solution.day());
+            assert_ne!(Some(input.clone()), generate(6), "day {}", solution.day());
+            if let Err(error) = solution.parse(&input) {
+                panic!("day {} generated unparsable input: {}", solution.day(), error);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_error_through_registry() {
+        let error = find(1).unwrap().parse("L68\nX30\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
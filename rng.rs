@@ -0,0 +1,97 @@
+use std::ops::RangeInclusive;
+
+/// Small deterministic pseudo-random generator (SplitMix64), so that a seed always
+/// reproduces the same test case or generated input
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..bound`; `bound` must be positive
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "empty range");
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+
+    /// Uniform value in `range`
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range");
+        let span = end.abs_diff(start);
+        match span.checked_add(1) {
+            Some(bound) => start.wrapping_add(self.below(bound) as i64),
+            None => self.next_u64() as i64,
+        }
+    }
+
+    /// Uniform index into a collection of `len` items
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// True with probability `numerator / denominator`
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Fisher-Yates shuffle in place
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.index(idx + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seed_reproduces_sequence() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let first: Vec<_> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(first, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(first, (0..5).map(|_| Rng::new(8).next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_ranges_stay_in_bounds() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!((-3..=4).contains(&rng.range(-3..=4)));
+            assert!(rng.below(10) < 10);
+            assert_eq!(rng.range(5..=5), 5);
+        }
+        rng.range(i64::MIN..=i64::MAX);
+
+        let seen: std::collections::HashSet<_> = (0..200).map(|_| *rng.choose(&['a', 'b', 'c'])).collect();
+        assert_eq!(seen.len(), 3);
+    }
+
+    #[test]
+    fn test_shuffle_permutes() {
+        let mut items: Vec<u32> = (0..20).collect();
+        Rng::new(3).shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
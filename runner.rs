@@ -0,0 +1,538 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::solution::{DynSolution, ParseError, ParsedInput};
+use crate::utils::{self, InputError, InputText};
+
+/// How the runner prints its results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Decorated, human-readable banners
+    Text,
+    /// A single JSON array holding one record per day and part
+    Json,
+    /// One JSON record per line, printed as soon as each day finishes
+    Ndjson,
+    /// Comma-separated values, only for `trace`
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("invalid format `{}` (expected text, json, ndjson or csv)", value)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: u8,
+    /// `None` when the day has no such part (day 12, part 2) or solving it panicked
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+    /// Message of the panic that stopped this part, if any
+    pub panic: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum DayError {
+    Read(InputError),
+    Parse(ParseError),
+    /// Parsing panicked, with the panic message
+    Panic(String),
+}
+
+/// Everything measured while running one day against one input
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: u8,
+    pub input_path: PathBuf,
+    pub input: InputText,
+    pub input_hash: Option<u64>,
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartReport>,
+    pub error: Option<DayError>,
+}
+
+impl DayReport {
+    fn new(day: u8, input_path: &Path) -> Self {
+        DayReport {
+            day,
+            input_path: input_path.to_path_buf(),
+            input: InputText::default(),
+            input_hash: None,
+            parse_elapsed: Duration::ZERO,
+            parts: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Whether the day or any of its parts failed
+    pub fn failed(&self) -> bool {
+        self.error.is_some() || self.parts.iter().any(|part| part.panic.is_some())
+    }
+
+    /// Time spent parsing and solving, excluding reading the input
+    pub fn elapsed(&self) -> Duration {
+        self.parse_elapsed + self.parts.iter().map(|part| part.elapsed).sum::<Duration>()
+    }
+}
+
+thread_local! {
+    /// Set while this thread runs solver code whose panics are caught and reported
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f`, returning the message of a panic instead of unwinding. The panic hook stays
+/// silent for it, so the message only shows up where the report puts it.
+fn catch_quietly<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                default(info);
+            }
+        }));
+    });
+
+    let was_quiet = QUIET.replace(true);
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    QUIET.set(was_quiet);
+    result.map_err(|payload| panic_message(&*payload))
+}
+
+fn solve_part(parsed: &dyn ParsedInput, part: u8) -> PartReport {
+    let start = Instant::now();
+    let solved = catch_quietly(|| match part {
+        1 => Some(parsed.part1()),
+        _ => parsed.part2(),
+    });
+    let elapsed = start.elapsed();
+    let (answer, panic) = match solved {
+        Ok(answer) => (answer, None),
+        Err(message) => (None, Some(message)),
+    };
+    PartReport {
+        part,
+        answer,
+        elapsed,
+        panic,
+    }
+}
+
+/// Read, parse and solve the selected parts (1 and/or 2) of one day
+pub fn run_day(solution: &dyn DynSolution, parts: &[u8], input_path: &Path) -> DayReport {
+    run_day_with(solution, parts, input_path, &AtomicUsize::new(0))
+}
+
+/// `run_day`, solving the later parts on threads of their own if `spare` has a free thread for
+/// each; otherwise the parts run in sequence. Each part is still timed on its own.
+fn run_day_with(solution: &dyn DynSolution, parts: &[u8], input_path: &Path, spare: &AtomicUsize) -> DayReport {
+    let mut report = DayReport::new(solution.day(), input_path);
+
+    report.input = match utils::try_map_input(input_path) {
+        Ok(input) => input,
+        Err(error) => {
+            report.error = Some(DayError::Read(error));
+            return report;
+        }
+    };
+    report.input_hash = Some(utils::fnv1a(report.input.as_bytes()));
+
+    let start = Instant::now();
+    let parsed = solution.parse(&report.input);
+    report.parse_elapsed = start.elapsed();
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            report.error = Some(DayError::Parse(error));
+            return report;
+        }
+    };
+
+    let parsed = parsed.as_ref();
+    let extra = parts.len().saturating_sub(1);
+    let borrowed = extra > 0 && spare.fetch_update(Ordering::AcqRel, Ordering::Acquire, |free| free.checked_sub(extra)).is_ok();
+    report.parts = if borrowed {
+        // The first part stays on this thread, which already counts against the pool
+        let reports = thread::scope(|scope| {
+            let handles: Vec<_> = parts[1..]
+                .iter()
+                .map(|&part| scope.spawn(move || solve_part(parsed, part)))
+                .collect();
+            let mut reports = vec![solve_part(parsed, parts[0])];
+            reports.extend(
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap_or_else(|payload| panic::resume_unwind(payload))),
+            );
+            reports
+        });
+        spare.fetch_add(extra, Ordering::AcqRel);
+        reports
+    } else {
+        parts.iter().map(|&part| solve_part(parsed, part)).collect()
+    };
+
+    report
+}
+
+/// Text of a panic payload, which is a `&str` or `String` for `panic!` with a message
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload.downcast_ref::<String>().cloned().unwrap_or_else(|| "unknown panic".to_string()),
+    }
+}
+
+/// `run_day_with`, reporting a panic while parsing as the day's error so the days after it
+/// still run; a panic in a part only fails that part
+fn run_day_caught(solution: &dyn DynSolution, parts: &[u8], input_path: &Path, spare: &AtomicUsize) -> DayReport {
+    catch_quietly(|| run_day_with(solution, parts, input_path, spare)).unwrap_or_else(|message| {
+        let mut report = DayReport::new(solution.day(), input_path);
+        report.error = Some(DayError::Panic(message));
+        report
+    })
+}
+
+/// Run several days on up to `jobs` threads, passing each report to `emit` in the order given
+/// Reports are emitted as soon as every earlier day has finished; with one job the days run
+/// one after another on the calling thread and the parts of a day are solved in sequence.
+/// A part that panics is reported with its message and no answer, without losing the others.
+/// At most `jobs` threads solve at once: a day only solves its parts side by side on a
+/// thread left idle, because there are fewer days than jobs or the other days are done.
+pub fn run_days(
+    days: &[(&'static dyn DynSolution, PathBuf)],
+    parts: &[u8],
+    jobs: usize,
+    mut emit: impl FnMut(DayReport),
+) {
+    if jobs <= 1 {
+        for (solution, input_path) in days {
+            emit(run_day_caught(*solution, parts, input_path, &AtomicUsize::new(0)));
+        }
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let workers = jobs.min(days.len());
+    // Threads of the pool not running a day, which a day may borrow for its second part
+    let spare = AtomicUsize::new(jobs - workers);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (next, spare) = (&next, &spare);
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((solution, input_path)) = days.get(index) else {
+                        break;
+                    };
+                    let report = run_day_caught(*solution, parts, input_path, spare);
+                    if sender.send((index, report)).is_err() {
+                        break;
+                    }
+                }
+                spare.fetch_add(1, Ordering::AcqRel);
+            });
+        }
+        drop(sender);
+
+        // Hold back reports that finish early until every earlier day has been emitted
+        let mut pending = BTreeMap::new();
+        let mut next_emit = 0;
+        for (index, report) in receiver {
+            pending.insert(index, report);
+            while let Some(report) = pending.remove(&next_emit) {
+                emit(report);
+                next_emit += 1;
+            }
+        }
+    });
+}
+
+/// Render a parse error like a compiler diagnostic, quoting the offending input line
+pub fn format_diagnostic(error: &ParseError, input_path: &Path, input: &str) -> String {
+    let source = utils::input_name(input_path);
+    let gutter = error.line.to_string().len();
+
+    let mut message = format!(
+        "error: expected {}, found {}\n{:gutter$}--> {}:{}:{}\n",
+        error.expected, error.found, "", source, error.line, error.column
+    );
+    if let Some(text) = input.lines().nth(error.line.saturating_sub(1)) {
+        message += &format!("{:gutter$} |\n", "");
+        message += &format!("{} | {}\n", error.line, text);
+        message += &format!("{:gutter$} | {:>column$}\n", "", "^", column = error.column);
+    }
+    message
+}
+
+/// Print a day in the decorated text format; errors go to stderr
+pub fn print_text(report: &DayReport, explicit_part: bool) {
+    println!("\n{:=<50}", "");
+    println!("Day {:02}", report.day);
+    println!("{:-<50}", "");
+
+    match &report.error {
+        Some(DayError::Read(error)) => {
+            eprintln!("error: cannot read input: {}", error);
+            if error.source.kind() == io::ErrorKind::NotFound {
+                eprintln!("  = help: save the puzzle input there, or pass `--input` / `--inputs-dir`");
+            }
+        }
+        Some(DayError::Parse(error)) => {
+            eprint!("{}", format_diagnostic(error, &report.input_path, &report.input));
+        }
+        Some(DayError::Panic(message)) => {
+            eprintln!("error: parsing panicked: {}", message);
+        }
+        None => {
+            for part in &report.parts {
+                match (&part.answer, &part.panic) {
+                    (_, Some(message)) => eprintln!("error: part {} panicked: {}", part.part, message),
+                    (Some(answer), None) => println!("Part {}: {}", part.part, answer),
+                    (None, None) if explicit_part => println!("Part {}: not available", part.part),
+                    (None, None) => {}
+                }
+            }
+            println!("{:-<50}", "");
+            println!("Time: {:?}", report.elapsed());
+        }
+    }
+
+    println!("{:=<50}", "");
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// One JSON object per solved part, or a single error record if the day failed
+pub fn json_records(report: &DayReport) -> Vec<String> {
+    let input = json_string(&utils::input_name(&report.input_path));
+    let input_hash = report
+        .input_hash
+        .map_or("null".to_string(), |hash| json_string(&format!("{:016x}", hash)));
+
+    let error = match &report.error {
+        Some(DayError::Read(error)) => Some(error.to_string()),
+        Some(DayError::Parse(error)) => Some(error.to_string()),
+        Some(DayError::Panic(message)) => Some(format!("parsing panicked: {}", message)),
+        None => None,
+    };
+    if let Some(error) = error {
+        return vec![format!(
+            r#"{{"day":{},"part":null,"answer":null,"duration_ns":null,"parse_ns":null,"input":{},"input_hash":{},"error":{}}}"#,
+            report.day,
+            input,
+            input_hash,
+            json_string(&error)
+        )];
+    }
+
+    report
+        .parts
+        .iter()
+        .filter(|part| part.answer.is_some() || part.panic.is_some())
+        .map(|part| {
+            let answer = part.answer.as_deref().map_or("null".to_string(), json_string);
+            let error = part
+                .panic
+                .as_ref()
+                .map_or("null".to_string(), |message| json_string(&format!("panicked: {}", message)));
+            format!(
+                r#"{{"day":{},"part":{},"answer":{},"duration_ns":{},"parse_ns":{},"input":{},"input_hash":{},"error":{}}}"#,
+                report.day,
+                part.part,
+                answer,
+                part.elapsed.as_nanos(),
+                report.parse_elapsed.as_nanos(),
+                input,
+                input_hash,
+                error
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_diagnostic() {
+        let error = ParseError::new(2, 1, "`L` or `R`", "`X`");
+        let message = format_diagnostic(&error, Path::new("day01/input.txt"), "L10\nX10\n");
+        assert_eq!(
+            message,
+            "error: expected `L` or `R`, found `X`\n \
+             --> day01/input.txt:2:1\n  \
+             |\n\
+             2 | X10\n  \
+             | ^\n"
+        );
+
+        let message = format_diagnostic(&error, Path::new("-"), "");
+        assert_eq!(message, "error: expected `L` or `R`, found `X`\n --> <stdin>:2:1\n");
+    }
+
+    #[test]
+    fn test_json_string_escapes() {
+        assert_eq!(json_string(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(json_string("line\n\u{1}"), r#""line\n\u0001""#);
+    }
+
+    #[test]
+    fn test_json_records() {
+        let mut report = DayReport::new(12, Path::new("day12/input.txt"));
+        report.input_hash = Some(0xabc);
+        report.parse_elapsed = Duration::from_nanos(5);
+        report.parts = vec![
+            PartReport { part: 1, answer: Some("403".to_string()), elapsed: Duration::from_nanos(70), panic: None },
+            PartReport { part: 2, answer: None, elapsed: Duration::ZERO, panic: None },
+        ];
+        assert_eq!(
+            json_records(&report),
+            vec![
+                r#"{"day":12,"part":1,"answer":"403","duration_ns":70,"parse_ns":5,"input":"day12/input.txt","input_hash":"0000000000000abc","error":null}"#
+            ]
+        );
+
+        report.error = Some(DayError::Parse(ParseError::new(1, 2, "a digit", "`x`")));
+        let records = json_records(&report);
+        assert_eq!(records.len(), 1);
+        assert!(records[0].contains(r#""part":null"#));
+        assert!(records[0].contains(r#""error":"line 1, column 2: expected a digit, found `x`""#));
+    }
+
+    #[test]
+    fn test_run_days_keeps_order() {
+        let dir = std::env::temp_dir().join(format!("aoc2025-run-days-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("day01.txt");
+        std::fs::write(&input, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n").unwrap();
+
+        let day01 = crate::registry::find(1).unwrap();
+        let day12 = crate::registry::find(12).unwrap();
+        let days = vec![
+            (day12, dir.join("missing.txt")),
+            (day01, input.clone()),
+            (day01, input.clone()),
+            (day12, dir.join("missing.txt")),
+        ];
+
+        for jobs in [1, 3] {
+            let mut reports = Vec::new();
+            run_days(&days, &[1, 2], jobs, |report| reports.push(report));
+            let order: Vec<u8> = reports.iter().map(|report| report.day).collect();
+            assert_eq!(order, [12, 1, 1, 12], "jobs = {}", jobs);
+            assert!(matches!(reports[0].error, Some(DayError::Read(_))));
+            let answers: Vec<_> = reports[1].parts.iter().map(|part| part.answer.as_deref()).collect();
+            assert_eq!(answers, [Some("3"), Some("6")]);
+        }
+
+        // Part 2 only takes a thread of its own when one is free, and gives it back after
+        for free in [0, 1, 2] {
+            let spare = AtomicUsize::new(free);
+            let report = run_day_with(day01, &[1, 2], &input, &spare);
+            let answers: Vec<_> = report.parts.iter().map(|part| part.answer.as_deref()).collect();
+            assert_eq!(answers, [Some("3"), Some("6")]);
+            assert_eq!(spare.load(Ordering::Acquire), free);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    struct Panics;
+
+    impl crate::solution::Solution for Panics {
+        const DAY: u8 = 13;
+
+        type Input = ();
+        type Answer1 = i64;
+        type Answer2 = i64;
+
+        fn parse(_input: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &()) -> i64 {
+            panic!("part 1 blew up")
+        }
+
+        fn part2(_input: &()) -> Option<i64> {
+            Some(7)
+        }
+    }
+
+    #[test]
+    fn test_run_days_reports_panics() {
+        let dir = std::env::temp_dir().join(format!("aoc2025-run-panics-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("day01.txt");
+        std::fs::write(&input, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82\n").unwrap();
+
+        let day01 = crate::registry::find(1).unwrap();
+        let days: Vec<(&'static dyn DynSolution, PathBuf)> = vec![(&Panics, input.clone()), (day01, input.clone())];
+
+        for jobs in [1, 3] {
+            let mut reports = Vec::new();
+            run_days(&days, &[1, 2], jobs, |report| reports.push(report));
+            let order: Vec<u8> = reports.iter().map(|report| report.day).collect();
+            assert_eq!(order, [13, 1], "jobs = {}", jobs);
+            // The part that panicked is reported, and the other part's answer is kept
+            assert!(reports[0].error.is_none() && reports[0].failed());
+            let parts: Vec<_> = reports[0].parts.iter().map(|part| (part.answer.as_deref(), part.panic.as_deref())).collect();
+            assert_eq!(parts, [(None, Some("part 1 blew up")), (Some("7"), None)]);
+            assert!(json_records(&reports[0])[0].contains(r#""answer":null"#));
+            assert!(json_records(&reports[0])[0].contains(r#""error":"panicked: part 1 blew up""#));
+            let answers: Vec<_> = reports[1].parts.iter().map(|part| part.answer.as_deref()).collect();
+            assert_eq!(answers, [Some("3"), Some("6")]);
+            assert!(!reports[1].failed());
+        }
+
+        // Part 2 on a borrowed thread still finishes, and the thread goes back to the pool
+        let spare = AtomicUsize::new(1);
+        let report = run_day_caught(&Panics, &[1, 2], &input, &spare);
+        assert_eq!(report.parts[1].answer.as_deref(), Some("7"));
+        assert_eq!(spare.load(Ordering::Acquire), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
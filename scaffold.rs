@@ -0,0 +1,326 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Last puzzle day of an Advent of Code event
+pub const LAST_DAY: u8 = 25;
+
+const SOLUTION_TEMPLATE: &str = r#"use crate::rng::Rng;
+use crate::solution::{ParseError, Solution};
+
+pub fn parse_input<I>(lines: I) -> Result<Vec<String>, ParseError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Ok(lines.into_iter().map(|line| line.as_ref().to_string()).collect())
+}
+
+pub fn part1(_lines: &[String]) -> i64 {
+    0
+}
+
+pub fn part2(_lines: &[String]) -> i64 {
+    0
+}
+
+pub struct Day{DD};
+
+impl Solution for Day{DD} {
+    const DAY: u8 = {D};
+
+    type Input = Vec<String>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input.lines())
+    }
+
+    fn part1(input: &Self::Input) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<i64> {
+        Some(part2(input))
+    }
+
+    // No generator yet: `aoc2025 gen` and the registry tests skip the day until this
+    // returns a random input in the puzzle's format
+    fn generate(_rng: &mut Rng, _scale: usize) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_examples() {
+        examples::assert_examples::<Day{DD}>(include_str!("README.md"));
+    }
+}
+"#;
+
+const README_TEMPLATE: &str = "# Day {DD}: TODO
+
+Rust solutions for the Advent of Code 2025 Day {DD} puzzle.
+
+- Part 1 TODO
+- Part 2 TODO
+
+## Example
+
+Paste the puzzle's example into the block and its answers into the fence line.
+
+```example part1=0 part2=0
+```
+
+Run the day's tests with `cargo test --release day{DD}`.
+";
+
+fn fill(template: &str, day: u8) -> String {
+    template
+        .replace("{DD}", &format!("{:02}", day))
+        .replace("{D}", &day.to_string())
+}
+
+/// Day number of a line registering a day, such as `pub mod day07;` or `&crate::day07::Day07,`
+fn registered_day(line: &str, prefix: &str, suffix: &str) -> Option<u8> {
+    line.trim()
+        .strip_prefix(prefix)?
+        .strip_suffix(suffix)?
+        .strip_prefix("day")?
+        .get(..2)?
+        .parse()
+        .ok()
+}
+
+/// Insert `new_lines` after the last line registering an earlier day, or otherwise before
+/// the first registered day, moved up by `attributes` lines so its attributes stay attached
+fn insert_in_order(
+    source: &str,
+    day: u8,
+    (prefix, suffix): (&str, &str),
+    attributes: usize,
+    new_lines: &[String],
+) -> Result<String, String> {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let registered: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| Some((idx, registered_day(line, prefix, suffix)?)))
+        .collect();
+
+    let at = match registered.iter().rev().find(|&&(_, registered)| registered < day) {
+        Some(&(idx, _)) => idx + 1,
+        None => match registered.first() {
+            Some(&(idx, _)) => idx.saturating_sub(attributes),
+            None => return Err(format!("no `{}dayNN{}` lines to add day {} next to", prefix, suffix, day)),
+        },
+    };
+    for (offset, line) in new_lines.iter().enumerate() {
+        lines.insert(at + offset, line);
+    }
+
+    let mut source = lines.join("\n");
+    source.push('\n');
+    Ok(source)
+}
+
+/// Add the `#[path]` module declaration of `day` to the source of `lib.rs`, in day order
+pub fn register_module(lib: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{:02}", day);
+    if lib.contains(&format!("pub mod {};", module)) {
+        return Err(format!("{} is already declared in lib.rs", module));
+    }
+    let declaration = [
+        format!("#[path = \"{}/solution.rs\"]", module),
+        format!("pub mod {};", module),
+    ];
+    insert_in_order(lib, day, ("pub mod ", ";"), 1, &declaration)
+}
+
+/// Add `day` to the `SOLUTIONS` list in the source of `registry.rs`, in day order
+pub fn register_solution(registry: &str, day: u8) -> Result<String, String> {
+    let entry = format!("    &crate::day{:02}::Day{:02},", day, day);
+    if registry.contains(entry.trim()) {
+        return Err(format!("day{:02} is already registered", day));
+    }
+    insert_in_order(registry, day, ("&crate::", ","), 0, &[entry])
+}
+
+/// Path next to `path` that its new contents are written to before replacing it
+fn staging_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".new");
+    path.with_file_name(name)
+}
+
+/// Write every file through a staging file, then rename them all into place. If a write
+/// fails no file is touched; if a rename fails the files already replaced get their
+/// `originals` back, so the tree is left as it was.
+fn write_all(files: &[(PathBuf, String)], originals: &[(PathBuf, String)]) -> Result<(), String> {
+    let mut staged = Vec::new();
+    for (path, contents) in files {
+        let staging = staging_path(path);
+        if let Err(error) = fs::write(&staging, contents) {
+            for staging in &staged {
+                let _ = fs::remove_file(staging);
+            }
+            return Err(format!("cannot write {}: {}", staging.display(), error));
+        }
+        staged.push(staging);
+    }
+
+    for (idx, ((path, _), staging)) in files.iter().zip(&staged).enumerate() {
+        if let Err(error) = fs::rename(staging, path) {
+            for staging in &staged[idx..] {
+                let _ = fs::remove_file(staging);
+            }
+            for (original, contents) in originals {
+                if files[..idx].iter().any(|(path, _)| path == original) {
+                    let _ = fs::write(original, contents);
+                }
+            }
+            return Err(format!("cannot write {}: {}", path.display(), error));
+        }
+    }
+    Ok(())
+}
+
+/// Create `dayNN/solution.rs` and `dayNN/README.md` under `root`, then declare the module
+/// in `root/lib.rs` and register the day in `root/registry.rs`; returns the files written.
+/// Every file is prepared before any is replaced, so a failure leaves `root` unchanged.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let dir = root.join(format!("day{:02}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let read = |path: &Path| fs::read_to_string(path).map_err(|error| format!("cannot read {}: {}", path.display(), error));
+    let lib_path = root.join("lib.rs");
+    let lib_source = read(&lib_path)?;
+    let lib = register_module(&lib_source, day)?;
+    let registry_path = root.join("registry.rs");
+    let registry_source = read(&registry_path)?;
+    let registry = register_solution(&registry_source, day)?;
+
+    let files = [
+        (dir.join("solution.rs"), fill(SOLUTION_TEMPLATE, day)),
+        (dir.join("README.md"), fill(README_TEMPLATE, day)),
+        (lib_path.clone(), lib),
+        (registry_path.clone(), registry),
+    ];
+    let originals = [(lib_path, lib_source), (registry_path, registry_source)];
+    fs::create_dir(&dir).map_err(|error| format!("cannot create {}: {}", dir.display(), error))?;
+    if let Err(error) = write_all(&files, &originals) {
+        // Without the directory a later run can create the day afresh
+        let _ = fs::remove_dir_all(&dir);
+        return Err(error);
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod solution;
+
+#[path = \"day02/solution.rs\"]
+pub mod day02;
+#[path = \"day05/solution.rs\"]
+pub mod day05;
+";
+
+    const REGISTRY: &str = "use crate::solution::DynSolution;
+
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &crate::day02::Day02,
+    &crate::day05::Day05,
+];
+
+#[cfg(test)]
+mod tests {}
+";
+
+    fn lines_with<'a>(source: &'a str, pattern: &str) -> Vec<&'a str> {
+        source.lines().filter(|line| line.contains(pattern)).collect()
+    }
+
+    #[test]
+    fn test_register_module_keeps_day_order() {
+        let source = register_module(LIB, 13).unwrap();
+        assert!(source.ends_with("pub mod day05;\n#[path = \"day13/solution.rs\"]\npub mod day13;\n"));
+
+        let source = register_module(&register_module(LIB, 3).unwrap(), 1).unwrap();
+        assert_eq!(
+            lines_with(&source, "mod day"),
+            ["pub mod day01;", "pub mod day02;", "pub mod day03;", "pub mod day05;"]
+        );
+        assert!(source.starts_with("pub mod solution;\n\n#[path = \"day01/solution.rs\"]\npub mod day01;\n#[path"));
+
+        assert!(register_module(LIB, 5).is_err());
+        assert!(register_module("pub mod solution;\n", 5).is_err());
+    }
+
+    #[test]
+    fn test_register_solution_keeps_day_order() {
+        let source = register_solution(REGISTRY, 4).unwrap();
+        assert_eq!(
+            lines_with(&source, "&crate::"),
+            ["    &crate::day02::Day02,", "    &crate::day04::Day04,", "    &crate::day05::Day05,"]
+        );
+        let source = register_solution(REGISTRY, 1).unwrap();
+        assert_eq!(lines_with(&source, "&crate::")[0], "    &crate::day01::Day01,");
+
+        assert_eq!(register_solution(REGISTRY, 5), Err("day05 is already registered".to_string()));
+    }
+
+    #[test]
+    fn test_new_day_writes_files() {
+        let root = std::env::temp_dir().join(format!("aoc2025-scaffold-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("lib.rs"), LIB).unwrap();
+        fs::write(root.join("registry.rs"), REGISTRY).unwrap();
+
+        let files = new_day(&root, 13).unwrap();
+        assert_eq!(files.len(), 4);
+        let solution = fs::read_to_string(root.join("day13/solution.rs")).unwrap();
+        assert!(solution.contains("pub struct Day13;"));
+        assert!(solution.contains("const DAY: u8 = 13;"));
+        // Registry-wide tests accept a day that opts out of generated inputs
+        assert!(solution.contains("fn generate(_rng: &mut Rng, _scale: usize) -> Option<String> {\n        None\n    }"));
+        assert!(fs::read_to_string(root.join("day13/README.md")).unwrap().starts_with("# Day 13: "));
+        assert!(fs::read_to_string(root.join("lib.rs")).unwrap().contains("pub mod day13;"));
+        assert!(fs::read_to_string(root.join("registry.rs")).unwrap().contains("&crate::day13::Day13,"));
+
+        assert!(new_day(&root, 13).unwrap_err().ends_with("already exists"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_new_day_failure_leaves_tree_unchanged() {
+        let root = std::env::temp_dir().join(format!("aoc2025-scaffold-fail-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("lib.rs"), LIB).unwrap();
+        fs::write(root.join("registry.rs"), REGISTRY).unwrap();
+        // A directory in the way of the registry's staging file makes the last write fail
+        fs::create_dir_all(root.join("registry.rs.new")).unwrap();
+
+        assert!(new_day(&root, 13).unwrap_err().starts_with("cannot write"));
+        assert_eq!(fs::read_to_string(root.join("lib.rs")).unwrap(), LIB);
+        assert_eq!(fs::read_to_string(root.join("registry.rs")).unwrap(), REGISTRY);
+        assert!(!root.join("day13").exists());
+        assert!(!root.join("lib.rs.new").exists());
+
+        // Once the obstacle is gone the same day can be created
+        fs::remove_dir(root.join("registry.rs.new")).unwrap();
+        assert_eq!(new_day(&root, 13).unwrap().len(), 4);
+        assert!(fs::read_to_string(root.join("registry.rs")).unwrap().contains("&crate::day13::Day13,"));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
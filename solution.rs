@@ -0,0 +1,162 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::rng::Rng;
+use crate::trace::Trace;
+
+/// Malformed puzzle input, located by 1-based line and column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>, found: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// Error pointing at `token`, which must be a slice of `text`, the contents of `line`
+    pub fn at(line: usize, text: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .saturating_sub(text.as_ptr() as usize)
+            .min(text.len());
+        let column = text[..offset].chars().count() + 1;
+        let found = match text[offset..].chars().next() {
+            _ if !token.is_empty() => format!("`{}`", token),
+            Some(next) => format!("`{}`", next),
+            None => "end of line".to_string(),
+        };
+        ParseError::new(line, column, expected, found)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `token`, a slice of `text` (the contents of `line`), reporting its position on failure
+pub fn parse_token<T: FromStr>(line: usize, text: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(line, text, token, expected))
+}
+
+/// A single day's puzzle: parse the raw input once, then solve each part from it
+pub trait Solution {
+    /// Puzzle day, used by the registry and for input lookup
+    const DAY: u8;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+
+    /// Days without a second puzzle (day 12) keep the default of `None`
+    fn part2(_input: &Self::Input) -> Option<Self::Answer2> {
+        None
+    }
+
+    /// Random input in this day's format, about `scale` times the size of a real one.
+    /// The same `rng` seed always yields the same input; `None` if the day has no generator.
+    fn generate(_rng: &mut Rng, _scale: usize) -> Option<String> {
+        None
+    }
+
+    /// Per-step breakdown of the answers for `aoc2025 trace`; `None` if the day has none
+    fn trace(_input: &Self::Input) -> Option<Trace> {
+        None
+    }
+}
+
+/// Parsed input of some day, with the answers rendered as text
+/// `Sync` so both parts can be solved on separate threads from the same parse
+pub trait ParsedInput: Sync {
+    fn part1(&self) -> String;
+    fn part2(&self) -> Option<String>;
+    fn trace(&self) -> Option<Trace>;
+}
+
+/// Object-safe view of a `Solution`, so the registry can hold every day in one list
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Result<Box<dyn ParsedInput>, ParseError>;
+    fn generate(&self, rng: &mut Rng, scale: usize) -> Option<String>;
+}
+
+struct Parsed<S: Solution>(S::Input);
+
+impl<S: Solution> ParsedInput for Parsed<S>
+where
+    S::Input: Sync,
+{
+    fn part1(&self) -> String {
+        S::part1(&self.0).to_string()
+    }
+
+    fn part2(&self) -> Option<String> {
+        S::part2(&self.0).map(|answer| answer.to_string())
+    }
+
+    fn trace(&self) -> Option<Trace> {
+        S::trace(&self.0)
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync + 'static,
+    S::Input: Sync + 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn ParsedInput>, ParseError> {
+        Ok(Box::new(Parsed::<S>(S::parse(input)?)))
+    }
+
+    fn generate(&self, rng: &mut Rng, scale: usize) -> Option<String> {
+        S::generate(rng, scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_points_at_token() {
+        let text = "12,x4,7";
+        let token = text.split(',').nth(1).unwrap();
+        let error = parse_token::<i64>(3, text, token, "a number").unwrap_err();
+        assert_eq!(error, ParseError::new(3, 4, "a number", "`x4`"));
+        assert_eq!(error.to_string(), "line 3, column 4: expected a number, found `x4`");
+    }
+
+    #[test]
+    fn test_error_at_end_of_line() {
+        let text = "12-";
+        let (_, token) = text.split_once('-').unwrap();
+        let error = ParseError::at(1, text, token, "a number");
+        assert_eq!(error.column, 4);
+        assert_eq!(error.found, "end of line");
+    }
+}
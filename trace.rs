@@ -0,0 +1,71 @@
+/// Step-by-step account of how a day reached its answers, one row per step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub columns: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Trace {
+    pub fn new(columns: Vec<&'static str>) -> Self {
+        Trace { columns, rows: Vec::new() }
+    }
+
+    /// Aligned table with a header line, every column right-aligned
+    pub fn to_text(&self) -> String {
+        let mut widths: Vec<usize> = self.columns.iter().map(|column| column.chars().count()).collect();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let line = |cells: &mut dyn Iterator<Item = &str>| {
+            let padded: Vec<String> = cells.zip(&widths).map(|(cell, &width)| format!("{:>width$}", cell)).collect();
+            padded.join("  ") + "\n"
+        };
+        let mut text = line(&mut self.columns.iter().copied());
+        for row in &self.rows {
+            text.push_str(&line(&mut row.iter().map(String::as_str)));
+        }
+        text
+    }
+
+    /// RFC 4180 CSV with a header record
+    pub fn to_csv(&self) -> String {
+        let field = |cell: &str| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        };
+
+        let mut csv = self.columns.iter().map(|column| field(column)).collect::<Vec<_>>().join(",") + "\n";
+        for row in &self.rows {
+            csv.push_str(&(row.iter().map(|cell| field(cell)).collect::<Vec<_>>().join(",") + "\n"));
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Trace {
+        let mut trace = Trace::new(vec!["move", "note"]);
+        trace.rows.push(vec!["1".to_string(), "plain".to_string()]);
+        trace.rows.push(vec!["12".to_string(), "a, \"quoted\" one".to_string()]);
+        trace
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(sample().to_text(), "move             note\n   1            plain\n  12  a, \"quoted\" one\n");
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(sample().to_csv(), "move,note\n1,plain\n12,\"a, \"\"quoted\"\" one\"\n");
+    }
+}
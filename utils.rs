@@ -1,36 +1,882 @@
+use std::fmt;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::ops::{Index, IndexMut};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::geometry::{Dir4, Dir8};
+use crate::solution::{parse_token, ParseError};
+
+/// Input path that reads from standard input instead of a file
+pub const STDIN_PATH: &str = "-";
+
+/// Failure to read an input, with the path and, for undecodable text, the 1-based line
+#[derive(Debug)]
+pub struct InputError {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub source: io::Error,
+}
+
+impl InputError {
+    fn new(path: &Path, line: Option<usize>, source: io::Error) -> Self {
+        InputError {
+            path: path.to_path_buf(),
+            line,
+            source,
+        }
+    }
+
+    fn invalid_utf8(path: &Path, line: usize) -> Self {
+        let source = io::Error::new(io::ErrorKind::InvalidData, "line is not valid UTF-8");
+        InputError::new(path, Some(line), source)
+    }
+
+    /// Text that reads but does not have the expected shape, e.g. a ragged grid
+    fn invalid_data(path: &Path, err: &ParseError) -> Self {
+        let message = format!("expected {}, found {}", err.expected, err.found);
+        InputError::new(path, Some(err.line), io::Error::new(io::ErrorKind::InvalidData, message))
+    }
+}
+
+/// Human-readable name of an input path, `<stdin>` for standard input
+pub fn input_name(path: &Path) -> String {
+    if path == Path::new(STDIN_PATH) {
+        "<stdin>".to_string()
+    } else {
+        path.display().to_string()
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", input_name(&self.path), line, self.source),
+            None => write!(f, "{}: {}", input_name(&self.path), self.source),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Standard input can only be consumed once, so it is buffered on first use
+/// and every later read (e.g. part 2 after part 1) sees the same contents
+fn stdin_bytes() -> io::Result<&'static [u8]> {
+    static STDIN: OnceLock<Result<Vec<u8>, (io::ErrorKind, String)>> = OnceLock::new();
+    let buffered = STDIN.get_or_init(|| {
+        let mut buffer = Vec::new();
+        io::stdin()
+            .read_to_end(&mut buffer)
+            .map(|_| buffer)
+            .map_err(|err| (err.kind(), err.to_string()))
+    });
+    match buffered {
+        Ok(buffer) => Ok(buffer),
+        Err((kind, message)) => Err(io::Error::new(*kind, message.clone())),
+    }
+}
+
+/// Compression format of an input, detected from its leading bytes or else its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    fn detect(path: &Path, head: &[u8]) -> Option<Self> {
+        if head.starts_with(&[0x1f, 0x8b]) {
+            return Some(Compression::Gzip);
+        }
+        if head.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            return Some(Compression::Zstd);
+        }
+        match path.extension()?.to_str()? {
+            "gz" => Some(Compression::Gzip),
+            "zst" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Wrap `reader` so it yields the decompressed text
+    #[cfg(feature = "compressed")]
+    fn decoder(self, reader: Box<dyn BufRead>) -> io::Result<Box<dyn BufRead>> {
+        Ok(match self {
+            Compression::Gzip => Box::new(BufReader::new(flate2::bufread::MultiGzDecoder::new(reader))),
+            Compression::Zstd => {
+                let decoder = ruzstd::decoding::StreamingDecoder::new(reader)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+                Box::new(BufReader::new(decoder))
+            }
+        })
+    }
+
+    #[cfg(not(feature = "compressed"))]
+    fn decoder(self, _reader: Box<dyn BufRead>) -> io::Result<Box<dyn BufRead>> {
+        let name = match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+        };
+        let message = format!("{} input needs a build with `--features compressed`", name);
+        Err(io::Error::new(io::ErrorKind::Unsupported, message))
+    }
+}
+
+/// Open an input for reading; `-` selects standard input. Gzip and zstd inputs are decompressed
+fn open_input(path: &Path) -> Result<Box<dyn BufRead>, InputError> {
+    let mut reader: Box<dyn BufRead> = if path == Path::new(STDIN_PATH) {
+        let bytes = stdin_bytes().map_err(|err| InputError::new(path, None, err))?;
+        Box::new(Cursor::new(bytes))
+    } else {
+        let file = File::open(path).map_err(|err| InputError::new(path, None, err))?;
+        Box::new(BufReader::new(file))
+    };
+
+    let head = reader.fill_buf().map_err(|err| InputError::new(path, None, err))?;
+    match Compression::detect(path, head) {
+        Some(compression) => compression.decoder(reader).map_err(|err| InputError::new(path, None, err)),
+        None => Ok(reader),
+    }
+}
+
+/// 64-bit FNV-1a, stable across platforms and Rust releases so hashes can be compared over time
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Split raw input into owned lines, handling both `\n` and `\r\n` endings
+pub fn parse_lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+/// Every integer in `text`, the contents of line `line_no`, skipping whatever separates them;
+/// `-` is a sign unless it follows a digit. A value `T` can't hold, such as one that overflows
+/// or a negative one for an unsigned `T`, is reported at its column rather than skipped.
+pub fn extract_ints<T: FromStr>(line_no: usize, text: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = text.as_bytes();
+    let mut values = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let signed = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        if !signed && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        values.push(parse_token(line_no, text, &text[start..i], "an integer")?);
+    }
+
+    Ok(values)
+}
+
+/// Run of non-blank lines, remembering where it starts in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// 1-based line number of `lines[0]`
+    pub first_line: usize,
+    pub lines: &'a [String],
+}
+
+impl<'a> Section<'a> {
+    /// Lines paired with their 1-based line numbers in the input
+    pub fn numbered(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        let first_line = self.first_line;
+        self.lines
+            .iter()
+            .enumerate()
+            .map(move |(offset, line)| (first_line + offset, line.as_str()))
+    }
+}
+
+/// Split lines into blocks separated by one or more blank lines
+pub fn split_sections(lines: &[String]) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut start = None;
+
+    for (idx, line) in lines.iter().enumerate() {
+        match (line.trim().is_empty(), start) {
+            (false, None) => start = Some(idx),
+            (true, Some(first)) => {
+                sections.push(Section { first_line: first + 1, lines: &lines[first..idx] });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(first) = start {
+        sections.push(Section { first_line: first + 1, lines: &lines[first..] });
+    }
+
+    sections
+}
+
+/// Non-blank lines tagged with a 0-based section index and their 1-based line number,
+/// for inputs streamed rather than collected for `split_sections`
+pub fn section_lines<I>(lines: I) -> impl Iterator<Item = (usize, usize, I::Item)>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut section = 0;
+    let mut in_section = false;
+    lines.into_iter().enumerate().filter_map(move |(idx, line)| {
+        if line.as_ref().trim().is_empty() {
+            if in_section {
+                section += 1;
+                in_section = false;
+            }
+            return None;
+        }
+        in_section = true;
+        Some((section, idx + 1, line))
+    })
+}
+
+/// Line layout such as `"{a range start}-{a range end}"`: literal text around `{description}` fields.
+///
+/// Each field runs up to the next occurrence of the literal after it, or to the end of the text
+/// for a trailing field, and is trimmed. `{{` and `}}` stand for literal braces. When a field
+/// fails to parse, the error expects that field's description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pattern {
+    template: &'static str,
+    name: Option<&'static str>,
+    /// The template split once, when the pattern is built, into `len` pieces
+    pieces: [Piece; MAX_PIECES],
+    len: usize,
+}
+
+/// Most fields and literals one pattern can hold
+const MAX_PIECES: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Piece {
+    /// Literal text as written in the template, with `{{` and `}}` still doubled
+    Literal(&'static str),
+    Field(&'static str),
+}
+
+/// `text[start..end]`, usable in a `const fn`
+const fn substring(text: &'static str, start: usize, end: usize) -> &'static str {
+    text.split_at(end).0.split_at(start).1
+}
+
+/// Split `template` into literals and fields; a malformed one fails to compile when the
+/// pattern is a `const`
+const fn compile(template: &'static str) -> ([Piece; MAX_PIECES], usize) {
+    let bytes = template.as_bytes();
+    let mut pieces = [Piece::Literal(""); MAX_PIECES];
+    let mut len = 0;
+    let mut literal_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if (bytes[i] == b'{' || bytes[i] == b'}') && i + 1 < bytes.len() && bytes[i + 1] == bytes[i] {
+            i += 2;
+            continue;
+        }
+        if bytes[i] != b'{' {
+            i += 1;
+            continue;
+        }
+
+        let mut end = i + 1;
+        while end < bytes.len() && bytes[end] != b'}' {
+            end += 1;
+        }
+        if end == bytes.len() {
+            panic!("unclosed field in pattern");
+        }
+        if len + 2 > MAX_PIECES {
+            panic!("too many fields in pattern");
+        }
+        if literal_start < i {
+            pieces[len] = Piece::Literal(substring(template, literal_start, i));
+            len += 1;
+        } else if len > 0 && matches!(pieces[len - 1], Piece::Field(_)) {
+            panic!("adjacent fields in pattern");
+        }
+        pieces[len] = Piece::Field(substring(template, i + 1, end));
+        len += 1;
+        i = end + 1;
+        literal_start = i;
+    }
+    if literal_start < bytes.len() {
+        if len == MAX_PIECES {
+            panic!("too many fields in pattern");
+        }
+        pieces[len] = Piece::Literal(substring(template, literal_start, bytes.len()));
+        len += 1;
+    }
+
+    (pieces, len)
+}
+
+/// Characters of a template literal, with doubled braces read once
+fn unescape(literal: &str) -> impl Iterator<Item = char> + '_ {
+    let mut chars = literal.chars();
+    std::iter::from_fn(move || {
+        let c = chars.next()?;
+        if c == '{' || c == '}' {
+            chars.next();
+        }
+        Some(c)
+    })
+}
+
+/// Length of the text `literal` matches at the start of `text`, if it matches there
+fn match_literal(text: &str, literal: &str) -> Option<usize> {
+    let mut rest = text;
+    for c in unescape(literal) {
+        rest = rest.strip_prefix(c)?;
+    }
+    Some(text.len() - rest.len())
+}
+
+/// Offset and length of the first text in `text` that `literal` matches
+fn find_literal(text: &str, literal: &str) -> Option<(usize, usize)> {
+    if !literal.contains(['{', '}']) {
+        return text.find(literal).map(|at| (at, literal.len()));
+    }
+    text.char_indices()
+        .find_map(|(at, _)| Some((at, match_literal(&text[at..], literal)?)))
+}
+
+impl Pattern {
+    pub const fn new(template: &'static str) -> Self {
+        let (pieces, len) = compile(template);
+        Pattern { template, name: None, pieces, len }
+    }
+
+    /// Report a missing literal as the whole text not matching `name` rather than the literal
+    pub const fn named(self, name: &'static str) -> Self {
+        Pattern { name: Some(name), ..self }
+    }
+
+    /// Error for a literal missing at `at`, a slice of `text`
+    fn missing_literal(&self, line_no: usize, line: &str, text: &str, at: &str, literal: &str) -> ParseError {
+        if let Some(name) = self.name {
+            return ParseError::at(line_no, line, text, name);
+        }
+        let literal: String = unescape(literal).collect();
+        let expected = match literal.trim() {
+            "" => "a space".to_string(),
+            shown => format!("`{}`", shown),
+        };
+        ParseError::at(line_no, line, &at[..0], expected)
+    }
+
+    /// Match `text`, a slice of `line`, collecting the fields up to the first mismatch
+    fn split<'t>(&self, line_no: usize, line: &'t str, text: &'t str) -> Matched<'t> {
+        let mut matched = Matched { line_no, line, fields: Vec::new(), missing: None };
+        let mut field = None;
+        let mut rest = text;
+
+        for &piece in &self.pieces[..self.len] {
+            let literal = match piece {
+                Piece::Field(name) => {
+                    field = Some(name);
+                    continue;
+                }
+                Piece::Literal(literal) => literal,
+            };
+            let (at, matched_len) = match field.take() {
+                Some(name) => match find_literal(rest, literal) {
+                    Some((at, matched_len)) => {
+                        matched.fields.push((name, rest[..at].trim()));
+                        (at, matched_len)
+                    }
+                    None => {
+                        // The open field takes the rest, so a bad value is reported before the missing literal
+                        matched.fields.push((name, rest.trim()));
+                        matched.missing = Some(self.missing_literal(line_no, line, text, &text[text.len()..], literal));
+                        break;
+                    }
+                },
+                None => match match_literal(rest, literal) {
+                    Some(matched_len) => (0, matched_len),
+                    None => {
+                        matched.missing = Some(self.missing_literal(line_no, line, text, rest, literal));
+                        break;
+                    }
+                },
+            };
+            rest = &rest[at + matched_len..];
+        }
+
+        if matched.missing.is_some() {
+            if self.name.is_some() {
+                matched.fields.clear();
+            }
+        } else if let Some(name) = field {
+            matched.fields.push((name, rest.trim()));
+        } else if !rest.is_empty() {
+            matched.missing = Some(ParseError::at(line_no, line, rest, "end of line"));
+        }
+        matched
+    }
+
+    /// Field texts of the whole `line`, trimmed, in pattern order
+    pub fn fields<'t, const N: usize>(&self, line_no: usize, line: &'t str) -> Result<[&'t str; N], ParseError> {
+        self.fields_in(line_no, line, line)
+    }
+
+    /// Like [`Pattern::fields`] for `text`, a slice of `line`
+    pub fn fields_in<'t, const N: usize>(&self, line_no: usize, line: &'t str, text: &'t str) -> Result<[&'t str; N], ParseError> {
+        let matched = self.split(line_no, line, text);
+        if let Some(err) = matched.missing {
+            return Err(err);
+        }
+        let texts: Vec<&str> = matched.fields.iter().map(|&(_, text)| text).collect();
+        Ok(texts
+            .try_into()
+            .unwrap_or_else(|_| panic!("pattern `{}` does not have {} fields", self.template, N)))
+    }
+
+    /// Parse the fields of the whole `line` into a tuple, one `FromStr` type per field
+    pub fn parse<T: FromFields>(&self, line_no: usize, line: &str) -> Result<T, ParseError> {
+        self.parse_in(line_no, line, line)
+    }
+
+    /// Like [`Pattern::parse`] for `text`, a slice of `line`, so columns stay relative to the line
+    pub fn parse_in<T: FromFields>(&self, line_no: usize, line: &str, text: &str) -> Result<T, ParseError> {
+        T::from_fields(self.split(line_no, line, text))
+    }
+}
+
+/// Fields matched by a [`Pattern`], before conversion
+#[derive(Debug)]
+pub struct Matched<'t> {
+    line_no: usize,
+    line: &'t str,
+    fields: Vec<(&'static str, &'t str)>,
+    missing: Option<ParseError>,
+}
+
+impl Matched<'_> {
+    /// Parse field `index`, or report why the pattern stopped matching before it
+    pub fn field<T: FromStr>(&self, index: usize) -> Result<T, ParseError> {
+        match self.fields.get(index) {
+            Some(&(name, text)) => parse_token(self.line_no, self.line, text, name),
+            None => Err(self.missing.clone().expect("pattern has fewer fields than requested")),
+        }
+    }
+
+    /// Check that the pattern matched to the end with exactly `count` fields
+    pub fn finish(self, count: usize) -> Result<(), ParseError> {
+        if let Some(err) = self.missing {
+            return Err(err);
+        }
+        assert_eq!(self.fields.len(), count, "pattern field count does not match the parsed tuple");
+        Ok(())
+    }
+}
+
+/// Tuple that a [`Pattern`] can parse into
+pub trait FromFields: Sized {
+    fn from_fields(matched: Matched<'_>) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_from_fields {
+    ($count:expr; $($index:tt: $ty:ident),+) => {
+        impl<$($ty: FromStr),+> FromFields for ($($ty,)+) {
+            fn from_fields(matched: Matched<'_>) -> Result<Self, ParseError> {
+                let value = ($(matched.field::<$ty>($index)?,)+);
+                matched.finish($count)?;
+                Ok(value)
+            }
+        }
+    };
+}
+
+impl_from_fields!(1; 0: A);
+impl_from_fields!(2; 0: A, 1: B);
+impl_from_fields!(3; 0: A, 1: B, 2: C);
+impl_from_fields!(4; 0: A, 1: B, 2: C, 3: D);
+
+/// Rectangular 2D grid stored row by row in a single `Vec`, indexed by `(row, col)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Grid {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width` columns over `cells` given row by row; panics unless they fill whole rows
+    pub fn new(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, cells.len(), "{} cells don't fill rows of {}", cells.len(), width);
+        Grid { width, height, cells }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Grid from nested rows; panics if the rows differ in length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|row| row.len() == width), "rows differ in length");
+        Grid::new(width, rows.into_iter().flatten().collect())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        (row < self.height && col < self.width).then(|| &self.cells[row * self.width + col])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        (row < self.height && col < self.width).then(|| &mut self.cells[row * self.width + col])
+    }
+
+    /// Position `offset` away from `(row, col)`, if it is inside the grid
+    pub fn offset(&self, (row, col): (usize, usize), (d_row, d_col): (isize, isize)) -> Option<(usize, usize)> {
+        let row = row.checked_add_signed(d_row).filter(|&row| row < self.height)?;
+        let col = col.checked_add_signed(d_col).filter(|&col| col < self.width)?;
+        Some((row, col))
+    }
+
+    /// Position one step in `dir` from `position`, if it is inside the grid
+    pub fn step(&self, position: (usize, usize), dir: impl Into<Dir8>) -> Option<(usize, usize)> {
+        self.offset(position, dir.into().grid_offset())
+    }
+
+    /// In-bounds orthogonal neighbours of `(row, col)`
+    pub fn neighbors4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir4::ALL.into_iter().filter_map(move |dir| self.step((row, col), dir))
+    }
+
+    /// In-bounds orthogonal and diagonal neighbours of `(row, col)`
+    pub fn neighbors8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir8::ALL.into_iter().filter_map(move |dir| self.step((row, col), dir))
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Position of the first cell (row by row) matching `predicate`
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let idx = self.cells.iter().position(predicate)?;
+        Some((idx / self.width, idx % self.width))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` because `chunks` rejects 0; a zero-width grid has no cells to chunk anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// Grid with rows and columns swapped
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the {}x{} grid", row, col, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the {}x{} grid", row, col, width, height))
+    }
+}
+
+/// One line per row, cells written back to back
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Build a rectangular grid from the lines of `input`, reporting the first cell `valid` rejects
+fn parse_grid_with(input: &str, valid: impl Fn(char) -> bool, expected: &str) -> Result<Grid<char>, ParseError> {
+    let mut width = None;
+    let mut cells = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        if let Some((col, c)) = line.char_indices().find(|&(_, c)| !valid(c)) {
+            return Err(ParseError::at(idx + 1, line, &line[col..col + c.len_utf8()], expected));
+        }
+
+        let start = cells.len();
+        cells.extend(line.chars());
+        let row_len = cells.len() - start;
+        match width {
+            Some(width) if row_len != width => {
+                return Err(ParseError::new(
+                    idx + 1,
+                    width.min(row_len) + 1,
+                    format!("a row of {} cells", width),
+                    format!("{} cells", row_len),
+                ));
+            }
+            _ => width = Some(row_len),
+        }
+    }
+
+    Ok(Grid::new(width.unwrap_or(0), cells))
+}
+
+/// Split raw input into a rectangular 2D grid whose cells are all listed in `allowed`
+pub fn parse_grid(input: &str, allowed: &str) -> Result<Grid<char>, ParseError> {
+    let expected = allowed
+        .chars()
+        .map(|c| format!("`{}`", c))
+        .collect::<Vec<_>>()
+        .join(" or ");
+    parse_grid_with(input, |c| allowed.contains(c), &expected)
+}
+
+/// Reads an input one line at a time into a single reused buffer, so scanning a file never
+/// holds more than its current line and allocates nothing per line; `-` reads standard input,
+/// which is buffered whole so it can be read again
+pub struct LineReader {
+    path: PathBuf,
+    reader: Box<dyn BufRead>,
+    buffer: Vec<u8>,
+    line: usize,
+}
+
+impl LineReader {
+    pub fn open<P: AsRef<Path>>(filename: P) -> Result<Self, InputError> {
+        let path = filename.as_ref();
+        Ok(LineReader {
+            path: path.to_path_buf(),
+            reader: open_input(path)?,
+            buffer: Vec::new(),
+            line: 0,
+        })
+    }
+
+    /// 1-based number of the line last read, 0 before the first
+    pub fn line_no(&self) -> usize {
+        self.line
+    }
+
+    /// Fill the buffer with the next line, minus its `\n` or `\r\n` ending
+    fn advance(&mut self) -> Option<Result<(), InputError>> {
+        self.buffer.clear();
+        match self.reader.read_until(b'\n', &mut self.buffer) {
+            Ok(0) => None,
+            Ok(_) => {
+                self.line += 1;
+                if self.buffer.ends_with(b"\n") {
+                    self.buffer.pop();
+                    if self.buffer.ends_with(b"\r") {
+                        self.buffer.pop();
+                    }
+                }
+                Some(Ok(()))
+            }
+            Err(err) => Some(Err(InputError::new(&self.path, Some(self.line + 1), err))),
+        }
+    }
+
+    /// The next line as raw bytes, valid until the following read, or `None` once the
+    /// input is exhausted
+    pub fn next_bytes(&mut self) -> Option<Result<&[u8], InputError>> {
+        Some(self.advance()?.map(|()| self.buffer.as_slice()))
+    }
+
+    /// The next line, which must be valid UTF-8, or `None` once the input is exhausted
+    pub fn next_line(&mut self) -> Option<Result<&str, InputError>> {
+        Some(self.advance()?.and_then(|()| self.text()))
+    }
+
+    fn text(&self) -> Result<&str, InputError> {
+        std::str::from_utf8(&self.buffer).map_err(|_| InputError::invalid_utf8(&self.path, self.line))
+    }
+}
+
+/// Call `f` with each line of an input and its 1-based line number, reading through one
+/// [`LineReader`] buffer. Stops at the first read error, or the first error `f` returns.
+pub fn try_for_each_line<P, E>(filename: P, mut f: impl FnMut(usize, &str) -> Result<(), E>) -> Result<(), E>
+where
+    P: AsRef<Path>,
+    E: From<InputError>,
+{
+    let mut reader = LineReader::open(filename)?;
+    while let Some(read) = reader.advance() {
+        read?;
+        f(reader.line, reader.text()?)?;
+    }
+    Ok(())
+}
+
+/// Read input file and return a vector of lines, or the reason it couldn't be read
+pub fn try_read_lines<P>(filename: P) -> Result<Vec<String>, InputError>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = LineReader::open(filename)?;
+    let mut lines = Vec::new();
+    while let Some(line) = reader.next_line() {
+        lines.push(line?.to_string());
+    }
+    Ok(lines)
+}
 
 /// Read input file and return a vector of lines
-/// Returns an empty vector if the file doesn't exist or is empty
+/// Returns an empty vector if the file can't be read; see `try_read_lines` for the reason
 pub fn read_lines<P>(filename: P) -> Vec<String>
 where
     P: AsRef<Path>,
 {
-    let file = match File::open(filename) {
-        Ok(f) => f,
-        Err(_) => return Vec::new(),
-    };
-    let reader = BufReader::new(file);
-    reader
-        .lines()
-        .map_while(Result::ok)
-        .collect()
+    try_read_lines(filename).unwrap_or_default()
+}
+
+/// Read input file and return a 2D grid of characters, or the reason it couldn't be read
+/// Rows of different lengths are reported as invalid data on the first short or long row
+pub fn try_read_grid<P>(filename: P) -> Result<Grid<char>, InputError>
+where
+    P: AsRef<Path>,
+{
+    let path = filename.as_ref();
+    let input = try_read_input(path)?;
+    parse_grid_with(&input, |_| true, "any character").map_err(|err| InputError::invalid_data(path, &err))
+}
+
+/// Stream an input into a grid of bytes, one byte per cell instead of a `char`'s four.
+/// Rows are read through one [`LineReader`] buffer, so only the grid itself takes memory.
+pub fn try_read_byte_grid<P>(filename: P) -> Result<Grid<u8>, InputError>
+where
+    P: AsRef<Path>,
+{
+    let path = filename.as_ref();
+    let mut reader = LineReader::open(path)?;
+    let mut width = None;
+    let mut cells = Vec::new();
+
+    while let Some(row) = reader.next_bytes() {
+        let start = cells.len();
+        cells.extend_from_slice(row?);
+        let row_len = cells.len() - start;
+        match width {
+            Some(width) if row_len != width => {
+                let err = ParseError::new(
+                    reader.line_no(),
+                    width.min(row_len) + 1,
+                    format!("a row of {} cells", width),
+                    format!("{} cells", row_len),
+                );
+                return Err(InputError::invalid_data(path, &err));
+            }
+            _ => width = Some(row_len),
+        }
+    }
+
+    Ok(Grid::new(width.unwrap_or(0), cells))
 }
 
 /// Read input file and return a 2D grid of characters
-#[allow(dead_code)]
-pub fn read_grid<P>(filename: P) -> Vec<Vec<char>>
+pub fn read_grid<P>(filename: P) -> Grid<char>
 where
     P: AsRef<Path>,
 {
-    let lines = read_lines(filename);
-    lines.iter().map(|line| line.chars().collect()).collect()
+    try_read_grid(filename).unwrap_or_default()
 }
 
 /// Read input file and return a vector of numbers (one per line)
-#[allow(dead_code)]
 pub fn read_numbers<P>(filename: P) -> Vec<i64>
 where
     P: AsRef<Path>,
@@ -42,12 +888,359 @@ where
         .collect()
 }
 
+/// Read input file as a single string, or the reason it couldn't be read
+pub fn try_read_input<P>(filename: P) -> Result<String, InputError>
+where
+    P: AsRef<Path>,
+{
+    let path = filename.as_ref();
+    let mut bytes = Vec::new();
+    open_input(path)?
+        .read_to_end(&mut bytes)
+        .map_err(|err| InputError::new(path, None, err))?;
+
+    String::from_utf8(bytes).map_err(|err| utf8_error(path, err.as_bytes(), err.utf8_error()))
+}
+
+/// Report undecodable input on the line holding the first bad byte
+fn utf8_error(path: &Path, bytes: &[u8], err: std::str::Utf8Error) -> InputError {
+    let valid = &bytes[..err.valid_up_to()];
+    let line = valid.iter().filter(|&&byte| byte == b'\n').count() + 1;
+    InputError::invalid_utf8(path, line)
+}
+
+/// Whole input text, read into memory or, with the `mmap` feature, mapped from its file
+#[derive(Debug, Default)]
+pub struct InputText(Backing);
+
+#[derive(Debug)]
+enum Backing {
+    Owned(String),
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Default for Backing {
+    fn default() -> Self {
+        Backing::Owned(String::new())
+    }
+}
+
+impl From<String> for InputText {
+    fn from(text: String) -> Self {
+        InputText(Backing::Owned(text))
+    }
+}
+
+impl std::ops::Deref for InputText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match &self.0 {
+            Backing::Owned(text) => text,
+            // SAFETY: checked to be UTF-8 when the file was mapped
+            #[cfg(feature = "mmap")]
+            Backing::Mapped(map) => unsafe { std::str::from_utf8_unchecked(map) },
+        }
+    }
+}
+
+/// Like `try_read_input`, but regular files are mapped into memory rather than copied
+/// when the crate is built with the `mmap` feature
+pub fn try_map_input<P>(filename: P) -> Result<InputText, InputError>
+where
+    P: AsRef<Path>,
+{
+    let path = filename.as_ref();
+    #[cfg(feature = "mmap")]
+    if path != Path::new(STDIN_PATH) {
+        let file = File::open(path).map_err(|err| InputError::new(path, None, err))?;
+        let metadata = file.metadata().map_err(|err| InputError::new(path, None, err))?;
+        // Empty files cannot be mapped, and pipes or devices have no fixed contents
+        if metadata.is_file() && metadata.len() > 0 {
+            // SAFETY: the map is read-only; inputs must not be modified while a day runs
+            let map = unsafe { memmap2::Mmap::map(&file) }.map_err(|err| InputError::new(path, None, err))?;
+            // Compressed files are decoded into memory like any other read
+            if Compression::detect(path, &map).is_none() {
+                if let Err(err) = std::str::from_utf8(&map) {
+                    return Err(utf8_error(path, &map, err));
+                }
+                return Ok(InputText(Backing::Mapped(map)));
+            }
+        }
+    }
+    try_read_input(path).map(InputText::from)
+}
+
 /// Read input file as a single string
-/// Returns an empty string if the file doesn't exist
-#[allow(dead_code)]
+/// Returns an empty string if the file can't be read; see `try_read_input` for the reason
 pub fn read_input<P>(filename: P) -> String
 where
     P: AsRef<Path>,
 {
-    std::fs::read_to_string(filename).unwrap_or_default()
+    try_read_input(filename).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        parse_grid("abc\ndef\n", "abcdef").unwrap()
+    }
+
+    #[test]
+    fn test_grid_access() {
+        let mut grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(1, 2), Some(&'f'));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid[(0, 1)], 'b');
+
+        grid[(0, 1)] = 'x';
+        *grid.get_mut(1, 0).unwrap() = 'y';
+        assert_eq!(grid.to_string(), "axc\nyef");
+        assert_eq!(grid.find(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.find(|&c| c == 'z'), None);
+    }
+
+    #[test]
+    fn test_grid_neighbors() {
+        let grid = Grid::filled(3, 3, 0);
+        let mut corner: Vec<_> = grid.neighbors4(0, 0).collect();
+        corner.sort_unstable();
+        assert_eq!(corner, [(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors8(0, 0).count(), 3);
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+        assert_eq!(grid.neighbors4(2, 1).count(), 3);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.step((0, 0), Dir4::Down), Some((1, 0)));
+        assert_eq!(grid.step((2, 2), Dir8::DownRight), None);
+    }
+
+    #[test]
+    fn test_grid_rows_columns_transpose() {
+        let grid = sample();
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+
+        let transposed = grid.transpose();
+        assert_eq!((transposed.width(), transposed.height()), (2, 3));
+        assert_eq!(transposed.to_string(), "ad\nbe\ncf");
+        assert_eq!(transposed.transpose(), grid);
+        assert_eq!(grid.map(|&c| c.to_ascii_uppercase()).row(1), ['D', 'E', 'F']);
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'e')));
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let grid = parse_grid("", ".").unwrap();
+        assert!(grid.is_empty());
+        assert_eq!(grid, Grid::default());
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.positions().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_extract_ints() {
+        assert_eq!(extract_ints::<i64>(1, "x=-3, y=14..-2 z:7-9"), Ok(vec![-3, 14, -2, 7, 9]));
+        assert_eq!(extract_ints::<u8>(1, "5 12-3"), Ok(vec![5, 12, 3]));
+        assert_eq!(extract_ints::<u8>(2, "1 -5 12"), Err(ParseError::new(2, 3, "an integer", "`-5`")));
+        assert_eq!(extract_ints::<u8>(3, "12, 300"), Err(ParseError::new(3, 5, "an integer", "`300`")));
+        assert_eq!(extract_ints::<i32>(4, "no numbers - here"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_split_sections() {
+        let lines = parse_lines("\na\nb\n\n\nc\n  \n");
+        let sections = split_sections(&lines);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].numbered().collect::<Vec<_>>(), [(2, "a"), (3, "b")]);
+        assert_eq!(sections[1].numbered().collect::<Vec<_>>(), [(6, "c")]);
+        assert!(split_sections(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_pattern_parse() {
+        const MOVE: Pattern = Pattern::new("move {a count} from {a source} to {a target}");
+        assert_eq!(MOVE.parse(1, "move 3 from 1 to 2"), Ok((3u32, 1u8, 2u8)));
+        assert_eq!(MOVE.parse::<(u32, u8, u8)>(1, "move x from 1 to 2"), Err(ParseError::new(1, 6, "a count", "`x`")));
+        assert_eq!(MOVE.parse::<(u32, u8, u8)>(2, "move 3 from 1"), Err(ParseError::new(2, 14, "`to`", "end of line")));
+        assert_eq!(MOVE.parse::<(u32, u8, u8)>(3, "take 3 from 1 to 2"), Err(ParseError::new(3, 1, "`move`", "`t`")));
+
+        const PAIR: Pattern = Pattern::new("{{{a key}}}={a value}").named("a pair `{key}=value`");
+        assert_eq!(
+            PAIR.pieces[..PAIR.len],
+            [Piece::Literal("{{"), Piece::Field("a key"), Piece::Literal("}}="), Piece::Field("a value")]
+        );
+        assert_eq!(PAIR.parse(1, "{ab}= 7"), Ok(("ab".to_string(), 7i64)));
+        assert_eq!(PAIR.parse::<(String, i64)>(1, "{ab} 7"), Err(ParseError::new(1, 1, "a pair `{key}=value`", "`{ab} 7`")));
+    }
+
+    #[test]
+    fn test_pattern_fields() {
+        const RANGE: Pattern = Pattern::new("{a start}-{an end}");
+        let line = "10-x: 5-9";
+        assert_eq!(RANGE.fields(1, "3 - 7"), Ok(["3", "7"]));
+        assert_eq!(RANGE.parse_in(4, line, &line[5..]), Ok((5, 9)));
+        assert_eq!(RANGE.fields_in(4, line, &line[5..]), Ok(["5", "9"]));
+        assert_eq!(RANGE.parse::<(i32, i32)>(4, line), Err(ParseError::new(4, 4, "an end", "`x: 5-9`")));
+        // A value that fails to parse is reported ahead of the separator missing after it
+        assert_eq!(RANGE.parse::<(i32, i32)>(5, "1x"), Err(ParseError::new(5, 1, "a start", "`1x`")));
+        assert_eq!(RANGE.parse::<(i32, i32)>(5, "12"), Err(ParseError::new(5, 3, "`-`", "end of line")));
+
+        const TERMINATED: Pattern = Pattern::new("<{a value}>");
+        assert_eq!(TERMINATED.fields::<1>(6, "<1>2"), Err(ParseError::new(6, 4, "end of line", "`2`")));
+    }
+
+    #[test]
+    fn test_section_lines() {
+        let lines = ["", "3-5", "", "", "1", "  ", "2"];
+        let tagged: Vec<_> = section_lines(lines).collect();
+        assert_eq!(tagged, [(0, 2, "3-5"), (1, 5, "1"), (2, 7, "2")]);
+    }
+
+    #[test]
+    fn test_read_errors_carry_context() {
+        let dir = std::env::temp_dir().join(format!("aoc2025-read-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let missing = dir.join("missing.txt");
+        let error = try_read_input(&missing).unwrap_err();
+        assert_eq!(error.source.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().starts_with(&missing.display().to_string()));
+
+        let invalid = dir.join("invalid.txt");
+        std::fs::write(&invalid, b"L10\nR5\nL\xff2\nR1\n").unwrap();
+        assert_eq!(try_read_input(&invalid).unwrap_err().line, Some(3));
+        assert_eq!(try_read_lines(&invalid).unwrap_err().line, Some(3));
+
+        let valid = dir.join("valid.txt");
+        std::fs::write(&valid, b"L10\r\nR5\n").unwrap();
+        assert_eq!(try_read_lines(&valid).unwrap(), vec!["L10", "R5"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_streaming_readers() {
+        let dir = std::env::temp_dir().join(format!("aoc2025-stream-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let lines = dir.join("lines.txt");
+        std::fs::write(&lines, b"ab\r\n\ncd").unwrap();
+        let mut reader = LineReader::open(&lines).unwrap();
+        assert_eq!(reader.next_line().unwrap().unwrap(), "ab");
+        assert_eq!(reader.next_bytes().unwrap().unwrap(), b"");
+        assert_eq!(reader.next_line().unwrap().unwrap(), "cd");
+        assert_eq!(reader.line_no(), 3);
+        assert!(reader.next_line().is_none());
+        assert_eq!(try_read_lines(&lines).unwrap(), ["ab", "", "cd"]);
+
+        let mut seen = Vec::new();
+        try_for_each_line(&lines, |line_no, line| {
+            seen.push((line_no, line.to_string()));
+            Ok::<_, InputError>(())
+        })
+        .unwrap();
+        assert_eq!(seen, [(1, "ab".to_string()), (2, String::new()), (3, "cd".to_string())]);
+        assert!(try_for_each_line(dir.join("missing.txt"), |_, _| Ok::<_, InputError>(())).is_err());
+        assert!(try_for_each_line(&dir, |_, _| Ok::<_, InputError>(())).is_err());
+
+        let invalid = dir.join("invalid.txt");
+        std::fs::write(&invalid, b"ok\n\xff\n").unwrap();
+        // Bytes are passed on as they are, so only the text readers reject them
+        let mut reader = LineReader::open(&invalid).unwrap();
+        assert!(reader.next_line().unwrap().is_ok());
+        assert_eq!(reader.next_bytes().unwrap().unwrap(), b"\xff");
+        let mut reader = LineReader::open(&invalid).unwrap();
+        assert!(reader.next_line().unwrap().is_ok());
+        assert_eq!(reader.next_line().unwrap().unwrap_err().line, Some(2));
+        let error = try_for_each_line(&invalid, |_, _| Ok::<_, InputError>(())).unwrap_err();
+        assert_eq!(error.line, Some(2));
+        assert_eq!(try_map_input(&invalid).unwrap_err().line, Some(2));
+
+        let grid = dir.join("grid.txt");
+        std::fs::write(&grid, b"#.\n.#\n").unwrap();
+        let bytes = try_read_byte_grid(&grid).unwrap();
+        assert_eq!((bytes.width(), bytes.height()), (2, 2));
+        assert_eq!(bytes[(1, 1)], b'#');
+        assert_eq!(&*try_map_input(&grid).unwrap(), "#.\n.#\n");
+
+        std::fs::write(&grid, b"#.\n#\n").unwrap();
+        let error = try_read_byte_grid(&grid).unwrap_err();
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.source.to_string(), "expected a row of 2 cells, found 1 cells");
+
+        let empty = dir.join("empty.txt");
+        std::fs::write(&empty, b"").unwrap();
+        assert_eq!(&*try_map_input(&empty).unwrap(), "");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_compression() {
+        assert_eq!(Compression::detect(Path::new("in.txt"), b"\x1f\x8b\x08"), Some(Compression::Gzip));
+        assert_eq!(Compression::detect(Path::new("-"), b"\x28\xb5\x2f\xfd"), Some(Compression::Zstd));
+        assert_eq!(Compression::detect(Path::new("in.txt.gz"), b""), Some(Compression::Gzip));
+        assert_eq!(Compression::detect(Path::new("in.zst"), b"L1"), Some(Compression::Zstd));
+        assert_eq!(Compression::detect(Path::new("in.txt"), b"L1\nR2"), None);
+    }
+
+    #[cfg(feature = "compressed")]
+    #[test]
+    fn test_compressed_inputs() {
+        use std::io::Write;
+
+        let dir = std::env::temp_dir().join(format!("aoc2025-compressed-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let text = "L68\nL30\nR48\n";
+
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(text.as_bytes()).unwrap();
+        let gz = dir.join("input.gz");
+        std::fs::write(&gz, gzip.finish().unwrap()).unwrap();
+
+        let zst = dir.join("input.bin");
+        let level = ruzstd::encoding::CompressionLevel::Fastest;
+        std::fs::write(&zst, ruzstd::encoding::compress_to_vec(text.as_bytes(), level)).unwrap();
+
+        for path in [&gz, &zst] {
+            assert_eq!(try_read_input(path).unwrap(), text);
+            assert_eq!(&*try_map_input(path).unwrap(), text);
+            assert_eq!(try_read_lines(path).unwrap(), ["L68", "L30", "R48"]);
+        }
+
+        let corrupt = dir.join("corrupt.gz");
+        std::fs::write(&corrupt, b"\x1f\x8b not really gzip").unwrap();
+        assert_eq!(try_read_input(&corrupt).unwrap_err().source.kind(), io::ErrorKind::InvalidInput);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(not(feature = "compressed"))]
+    #[test]
+    fn test_compressed_inputs_need_feature() {
+        let dir = std::env::temp_dir().join(format!("aoc2025-compressed-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let gz = dir.join("input.txt.gz");
+        std::fs::write(&gz, b"\x1f\x8b\x08\x00").unwrap();
+        let error = try_read_input(&gz).unwrap_err();
+        assert_eq!(error.source.kind(), io::ErrorKind::Unsupported);
+        assert!(error.to_string().contains("--features compressed"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
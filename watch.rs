@@ -0,0 +1,214 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How often `aoc2025 watch` looks at the watched files
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification time and length of a file, or `None` while it doesn't exist
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), metadata.len()))
+}
+
+/// Set of files polled for changes, with no platform-specific notifier
+#[derive(Debug)]
+pub struct Watcher {
+    files: Vec<(PathBuf, Stamp)>,
+}
+
+impl Watcher {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let files = paths.into_iter().map(|path| {
+            let stamp = stamp(&path);
+            (path, stamp)
+        });
+        Watcher { files: files.collect() }
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+
+    /// Files created, modified or removed since the previous call (or since `new`)
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, last) in &mut self.files {
+            let current = stamp(path);
+            if current != *last {
+                *last = current;
+                changed.push(path.clone());
+            }
+        }
+        changed
+    }
+}
+
+/// Files that affect a day's results: its source, the README holding its examples, any
+/// other `example*` file in its directory, and its input
+pub fn day_files(source_dir: &Path, day: u8, input_path: &Path) -> Vec<PathBuf> {
+    let dir = source_dir.join(format!("day{:02}", day));
+    let mut examples: Vec<PathBuf> = fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with("example")))
+        .collect();
+    examples.sort();
+
+    let mut files = vec![dir.join("solution.rs"), dir.join("README.md")];
+    files.extend(examples);
+    files.push(input_path.to_path_buf());
+    files
+}
+
+/// Answers of one run, read back from `aoc2025 --format ndjson` output
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunAnswers {
+    pub parts: Vec<(u8, String)>,
+    pub error: Option<String>,
+}
+
+/// Value of `key` in one flat JSON record as written by `runner::json_records`: strings are
+/// unescaped, other values are returned as written and `null` as `None`
+fn json_field(record: &str, key: &str) -> Option<String> {
+    let start = record.find(&format!("\"{}\":", key))? + key.len() + 3;
+    let rest = &record[start..];
+    let Some(quoted) = rest.strip_prefix('"') else {
+        let value = &rest[..rest.find([',', '}']).unwrap_or(rest.len())];
+        return (value != "null").then(|| value.to_string());
+    };
+
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    value.push(u32::from_str_radix(&code, 16).ok().and_then(char::from_u32)?);
+                }
+                escaped => value.push(escaped),
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+pub fn parse_ndjson(output: &str) -> RunAnswers {
+    let mut answers = RunAnswers::default();
+    for record in output.lines().filter(|line| line.starts_with('{')) {
+        if let Some(error) = json_field(record, "error") {
+            answers.error = Some(error);
+        }
+        let part = json_field(record, "part").and_then(|part| part.parse().ok());
+        if let (Some(part), Some(answer)) = (part, json_field(record, "answer")) {
+            answers.parts.push((part, answer));
+        }
+    }
+    answers
+}
+
+/// One line per part saying how its answer moved since `previous`, e.g. `part 1: 12 -> 15`
+pub fn diff_answers(previous: Option<&RunAnswers>, current: &RunAnswers) -> Vec<String> {
+    let before = |part: u8| {
+        previous.and_then(|previous| previous.parts.iter().find(|(number, _)| *number == part).map(|(_, answer)| answer))
+    };
+
+    let mut lines = Vec::new();
+    if let Some(error) = &current.error {
+        lines.push(format!("error: {}", error));
+    }
+    for (part, answer) in &current.parts {
+        lines.push(match before(*part) {
+            Some(old) if old == answer => format!("part {}: {} (unchanged)", part, answer),
+            Some(old) => format!("part {}: {} -> {}", part, old, answer),
+            None => format!("part {}: {}", part, answer),
+        });
+    }
+    for (part, old) in previous.map_or(&[][..], |previous| &previous.parts) {
+        if !current.parts.iter().any(|(number, _)| number == part) {
+            lines.push(format!("part {}: {} -> no answer", part, old));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watcher_sees_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc2025-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (source, input) = (dir.join("solution.rs"), dir.join("input.txt"));
+        fs::write(&source, "fn main() {}").unwrap();
+        let _ = fs::remove_file(&input);
+
+        let mut watcher = Watcher::new([source.clone(), input.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&input, "1\n").unwrap();
+        assert_eq!(watcher.changed(), std::slice::from_ref(&input));
+
+        // Same length, so only the modification time gives the edit away
+        let file = fs::File::options().write(true).open(&source).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+        drop(file);
+        fs::remove_file(&input).unwrap();
+        assert_eq!(watcher.changed(), [source, input]);
+        assert!(watcher.changed().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_day_files() {
+        let files = day_files(Path::new(env!("CARGO_MANIFEST_DIR")), 1, Path::new("in/day01.txt"));
+        let names: Vec<_> = files.iter().map(|path| path.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, ["solution.rs", "README.md", "day01.txt"]);
+    }
+
+    #[test]
+    fn test_parse_ndjson() {
+        let output = r#"{"day":1,"part":1,"answer":"3","duration_ns":5,"parse_ns":7,"input":"a","input_hash":"00","error":null}
+{"day":1,"part":2,"answer":"x\"y!","duration_ns":5,"parse_ns":7,"input":"a","input_hash":"00","error":null}
+"#;
+        let answers = parse_ndjson(output);
+        assert_eq!(answers.parts, [(1, "3".to_string()), (2, "x\"y!".to_string())]);
+        assert_eq!(answers.error, None);
+
+        let failed = r#"{"day":1,"part":null,"answer":null,"duration_ns":null,"parse_ns":null,"input":"a","input_hash":null,"error":"line 2, column 1: expected `L` or `R`, found `X`"}"#;
+        let answers = parse_ndjson(failed);
+        assert!(answers.parts.is_empty());
+        assert_eq!(answers.error.as_deref(), Some("line 2, column 1: expected `L` or `R`, found `X`"));
+    }
+
+    #[test]
+    fn test_diff_answers() {
+        let run = |parts: &[(u8, &str)]| RunAnswers {
+            parts: parts.iter().map(|&(part, answer)| (part, answer.to_string())).collect(),
+            error: None,
+        };
+        let first = run(&[(1, "3"), (2, "6")]);
+        assert_eq!(diff_answers(None, &first), ["part 1: 3", "part 2: 6"]);
+        assert_eq!(
+            diff_answers(Some(&first), &run(&[(1, "3"), (2, "7")])),
+            ["part 1: 3 (unchanged)", "part 2: 6 -> 7"]
+        );
+
+        let failed = RunAnswers { parts: Vec::new(), error: Some("bad input".to_string()) };
+        assert_eq!(
+            diff_answers(Some(&first), &failed),
+            ["error: bad input", "part 1: 3 -> no answer", "part 2: 6 -> no answer"]
+        );
+    }
+}